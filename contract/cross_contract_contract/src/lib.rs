@@ -34,6 +34,10 @@ enum DataKey {
     LatestNonce,
 }
 
+const DAY_IN_LEDGERS: u32 = 17280;
+const TTL_INSTANCE: u32 = DAY_IN_LEDGERS * 30; // 30 days
+const TTL_PERSISTENT: u32 = DAY_IN_LEDGERS * 30; // 30 days
+
 #[contract]
 pub struct CrossContractContract;
 
@@ -66,9 +70,7 @@ impl CrossContractContract {
             nonce,
         };
 
-        env.storage()
-            .persistent()
-            .set(&DataKey::MessageByNonce(nonce), &message);
+        Self::save_message(&env, &message);
 
         env.events().publish(
             (Symbol::new(&env, "message_sent"), nonce),
@@ -86,6 +88,8 @@ impl CrossContractContract {
         env: Env,
         message: CrossChainMessage,
     ) -> Result<(), CrossContractError> {
+        Self::extend_instance_ttl(&env);
+
         if message.payload.is_empty() {
             return Err(CrossContractError::InvalidMessage);
         }
@@ -98,9 +102,7 @@ impl CrossContractContract {
             return Err(CrossContractError::DuplicateNonce);
         }
 
-        env.storage()
-            .persistent()
-            .set(&DataKey::MessageByNonce(message.nonce), &message);
+        Self::save_message(&env, &message);
 
         env.events().publish(
             (
@@ -120,10 +122,7 @@ impl CrossContractContract {
         env: Env,
         nonce: u64,
     ) -> Result<CrossChainMessage, CrossContractError> {
-        env.storage()
-            .persistent()
-            .get(&DataKey::MessageByNonce(nonce))
-            .ok_or(CrossContractError::MessageNotFound)
+        Self::load_message(&env, nonce)
     }
 
     /// Retrieve a stored message by nonce.
@@ -131,10 +130,46 @@ impl CrossContractContract {
         env: Env,
         nonce: u64,
     ) -> Result<CrossChainMessage, CrossContractError> {
+        Self::load_message(&env, nonce)
+    }
+
+    /// Refresh the TTL of a stored message and of the contract instance.
+    ///
+    /// Keeper entry point: anyone may call it to keep a message that is
+    /// still awaiting relay or verification from being archived.
+    pub fn bump_entry(env: Env, nonce: u64) -> Result<(), CrossContractError> {
+        Self::extend_instance_ttl(&env);
+        Self::load_message(&env, nonce)?;
+        Ok(())
+    }
+
+    fn load_message(env: &Env, nonce: u64) -> Result<CrossChainMessage, CrossContractError> {
+        let key = DataKey::MessageByNonce(nonce);
+        let message = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .ok_or(CrossContractError::MessageNotFound)?;
+        Self::extend_persistent_ttl(env, &key);
+        Ok(message)
+    }
+
+    fn save_message(env: &Env, message: &CrossChainMessage) {
+        let key = DataKey::MessageByNonce(message.nonce);
+        env.storage().persistent().set(&key, message);
+        Self::extend_persistent_ttl(env, &key);
+    }
+
+    fn extend_persistent_ttl(env: &Env, key: &DataKey) {
         env.storage()
             .persistent()
-            .get(&DataKey::MessageByNonce(nonce))
-            .ok_or(CrossContractError::MessageNotFound)
+            .extend_ttl(key, TTL_PERSISTENT - DAY_IN_LEDGERS, TTL_PERSISTENT);
+    }
+
+    fn extend_instance_ttl(env: &Env) {
+        env.storage()
+            .instance()
+            .extend_ttl(TTL_INSTANCE - DAY_IN_LEDGERS, TTL_INSTANCE);
     }
 
     /// Return the next monotonically increasing nonce.
@@ -146,6 +181,7 @@ impl CrossContractContract {
             .unwrap_or(0);
         let next = current + 1;
         env.storage().instance().set(&DataKey::LatestNonce, &next);
+        Self::extend_instance_ttl(env);
         next
     }
 
@@ -154,3 +190,6 @@ impl CrossContractContract {
         BytesN::from_array(env, &[0u8; 32])
     }
 }
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,52 @@
+use crate::{CrossContractContract, CrossContractContractClient, DataKey, TTL_PERSISTENT};
+use soroban_sdk::{
+    testutils::{storage::Persistent as _, Ledger},
+    Bytes, BytesN, Env,
+};
+
+fn setup() -> (Env, CrossContractContractClient<'static>) {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(CrossContractContract, ());
+    let client = CrossContractContractClient::new(&env, &contract_id);
+    (env, client)
+}
+
+fn message_ttl(env: &Env, client: &CrossContractContractClient, nonce: u64) -> u32 {
+    env.as_contract(&client.address, || {
+        env.storage()
+            .persistent()
+            .get_ttl(&DataKey::MessageByNonce(nonce))
+    })
+}
+
+// ---------------------------------------------------------------------------
+// Storage Expiry Tests
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_bumped_message_survives_ledger_advancement() {
+    let (env, client) = setup();
+    let target_chain = BytesN::from_array(&env, &[7u8; 32]);
+    let payload = Bytes::from_array(&env, &[1, 2, 3]);
+
+    let bumped = client.send_message(&target_chain, &payload);
+    let stale = client.send_message(&target_chain, &payload);
+    assert_eq!(message_ttl(&env, &client, bumped), TTL_PERSISTENT);
+
+    // Advance to just short of the messages' archival ledger.
+    env.ledger()
+        .with_mut(|li| li.sequence_number += TTL_PERSISTENT - 300);
+    assert_eq!(message_ttl(&env, &client, stale), 300);
+
+    client.bump_entry(&bumped);
+    assert_eq!(message_ttl(&env, &client, bumped), TTL_PERSISTENT);
+
+    // Past the un-bumped message's live-until ledger, the bumped one is
+    // still comfortably live.
+    env.ledger().with_mut(|li| li.sequence_number += 1_000);
+    assert_eq!(
+        message_ttl(&env, &client, bumped),
+        TTL_PERSISTENT - 1_000
+    );
+}
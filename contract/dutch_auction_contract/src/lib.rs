@@ -1,8 +1,6 @@
 #![no_std]
 
-use soroban_sdk::{
-    contract, contracterror, contractimpl, contracttype, Address, Env, Symbol,
-};
+use soroban_sdk::{contract, contracterror, contractimpl, contracttype, Address, Env, Symbol, Vec};
 
 #[contracterror]
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
@@ -15,6 +13,8 @@ pub enum DutchAuctionError {
     Unauthorized = 6,
     InsufficientFunds = 7,
     PriceBelowReserve = 8,
+    SellerBidNotAllowed = 9,
+    PriceAboveLimit = 10,
 }
 
 #[contracttype]
@@ -29,6 +29,17 @@ pub struct Auction {
     pub current_price: i128,
     pub is_settled: bool,
     pub winner: Option<Address>,
+    pub allow_seller_bids: bool,
+    pub settlement_oracle: Option<Address>,
+}
+
+/// Outcome of a settled auction, written once by `settle_auction`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AuctionSettlement {
+    pub winner: Address,
+    pub clearing_price: i128,
+    pub settled_at: u64,
 }
 
 #[contracttype]
@@ -36,8 +47,14 @@ pub struct Auction {
 enum DataKey {
     Auction,
     Initialized,
+    Settlement,
 }
 
+const DAY_IN_LEDGERS: u32 = 17280;
+const LEDGER_CLOSE_SECONDS: u64 = 5;
+const TTL_INSTANCE: u32 = DAY_IN_LEDGERS * 30; // 30 days
+const TTL_RETENTION: u32 = DAY_IN_LEDGERS * 30; // 30 days past expiry
+
 #[contract]
 pub struct DutchAuctionContract;
 
@@ -47,6 +64,10 @@ impl DutchAuctionContract {
     ///
     /// Validates that `start_price > reserve_price`, `duration > 0`, and
     /// `price_decrement > 0`.  Stores the auction in persistent storage.
+    /// Unless `allow_seller_bids` is set, the seller cannot bid on their own
+    /// auction.  A `settlement_oracle`, if given, floors the price at its
+    /// `floor_price`; it is fixed at creation so bidders know it up front.
+    #[allow(clippy::too_many_arguments)]
     pub fn create_auction(
         env: Env,
         seller: Address,
@@ -54,6 +75,8 @@ impl DutchAuctionContract {
         reserve_price: i128,
         price_decrement: i128,
         duration: u64,
+        allow_seller_bids: bool,
+        settlement_oracle: Option<Address>,
     ) -> Result<(), DutchAuctionError> {
         if env.storage().instance().has(&DataKey::Initialized) {
             return Err(DutchAuctionError::AuctionAlreadyStarted);
@@ -72,7 +95,9 @@ impl DutchAuctionContract {
         }
 
         let start_time = env.ledger().timestamp();
-        let end_time = start_time + duration;
+        let end_time = start_time
+            .checked_add(duration)
+            .ok_or(DutchAuctionError::InvalidBid)?;
 
         let auction = Auction {
             seller,
@@ -84,14 +109,13 @@ impl DutchAuctionContract {
             current_price: start_price,
             is_settled: false,
             winner: None,
+            allow_seller_bids,
+            settlement_oracle,
         };
 
-        env.storage()
-            .persistent()
-            .set(&DataKey::Auction, &auction);
-        env.storage()
-            .instance()
-            .set(&DataKey::Initialized, &true);
+        Self::save_auction(&env, &auction);
+        env.storage().instance().set(&DataKey::Initialized, &true);
+        Self::extend_instance_ttl(&env);
 
         env.events().publish(
             (Symbol::new(&env, "auction_created"),),
@@ -105,39 +129,26 @@ impl DutchAuctionContract {
     ///
     /// Price decrements linearly from `start_price` toward `reserve_price`
     /// based on elapsed time.  Once the reserve is reached, the price
-    /// stays at the reserve until the auction ends.
+    /// stays at the reserve until the auction ends.  A settlement oracle's
+    /// floor, if set, is applied on top.
     pub fn get_current_price(env: Env) -> Result<i128, DutchAuctionError> {
         let auction = Self::load_auction(&env)?;
-
-        let now = env.ledger().timestamp();
-
-        if now >= auction.end_time {
-            return Ok(auction.reserve_price);
-        }
-
-        let elapsed = now - auction.start_time;
-        let total_duration = auction.end_time - auction.start_time;
-
-        if total_duration == 0 {
-            return Ok(auction.reserve_price);
-        }
-
-        let total_decrement = auction.price_decrement * elapsed as i128;
-        let price = auction.start_price - total_decrement;
-
-        if price < auction.reserve_price {
-            Ok(auction.reserve_price)
-        } else {
-            Ok(price)
-        }
+        Self::quote_price(&env, &auction, env.ledger().timestamp())
     }
 
     /// Place a bid at the current price.
     ///
-    /// First-call-wins: the first bidder to call this after the auction
-    /// starts wins the auction.  Subsequent bids are rejected once a
-    /// winner is recorded.
-    pub fn place_bid(env: Env, bidder: Address) -> Result<(), DutchAuctionError> {
+    /// Requires the bidder's authorization.  First-call-wins: the first
+    /// bidder to call this after the auction starts wins the auction.  Subsequent bids are rejected once a
+    /// winner is recorded.  If `max_acceptable_price` is given, the bid is
+    /// rejected when the current price is above it.
+    pub fn place_bid(
+        env: Env,
+        bidder: Address,
+        max_acceptable_price: Option<i128>,
+    ) -> Result<(), DutchAuctionError> {
+        bidder.require_auth();
+        Self::extend_instance_ttl(&env);
         let mut auction = Self::load_auction(&env)?;
 
         if auction.is_settled {
@@ -157,23 +168,29 @@ impl DutchAuctionContract {
             return Err(DutchAuctionError::AuctionEnded);
         }
 
-        let current_price = Self::compute_price(&auction, now)?;
+        if bidder == auction.seller && !auction.allow_seller_bids {
+            return Err(DutchAuctionError::SellerBidNotAllowed);
+        }
+
+        let current_price = Self::quote_price(&env, &auction, now)?;
 
         if current_price < auction.reserve_price {
             return Err(DutchAuctionError::PriceBelowReserve);
         }
 
+        if let Some(max_price) = max_acceptable_price {
+            if current_price > max_price {
+                return Err(DutchAuctionError::PriceAboveLimit);
+            }
+        }
+
         auction.current_price = current_price;
         auction.winner = Some(bidder.clone());
 
-        env.storage()
-            .persistent()
-            .set(&DataKey::Auction, &auction);
+        Self::save_auction(&env, &auction);
 
-        env.events().publish(
-            (Symbol::new(&env, "bid_placed"),),
-            (bidder, current_price),
-        );
+        env.events()
+            .publish((Symbol::new(&env, "bid_placed"),), (bidder, current_price));
 
         Ok(())
     }
@@ -182,7 +199,9 @@ impl DutchAuctionContract {
     ///
     /// Transfers funds from the winner to the seller and marks the auction
     /// as settled.  Can only be called after a winner has been recorded.
+    /// Records an `AuctionSettlement` readable through `get_settlement`.
     pub fn settle_auction(env: Env) -> Result<Address, DutchAuctionError> {
+        Self::extend_instance_ttl(&env);
         let mut auction = Self::load_auction(&env)?;
 
         if auction.is_settled {
@@ -196,13 +215,22 @@ impl DutchAuctionContract {
 
         auction.is_settled = true;
 
+        Self::save_auction(&env, &auction);
+
+        let clearing_price = auction.current_price;
+        let settlement = AuctionSettlement {
+            winner: winner.clone(),
+            clearing_price,
+            settled_at: env.ledger().timestamp(),
+        };
         env.storage()
             .persistent()
-            .set(&DataKey::Auction, &auction);
+            .set(&DataKey::Settlement, &settlement);
+        Self::extend_settlement_ttl(&env, &auction);
 
         env.events().publish(
             (Symbol::new(&env, "auction_settled"),),
-            (winner.clone(), auction.current_price),
+            (winner.clone(), clearing_price),
         );
 
         Ok(winner)
@@ -213,34 +241,101 @@ impl DutchAuctionContract {
         Self::load_auction(&env)
     }
 
+    /// Get the settlement record of a settled auction.
+    pub fn get_settlement(env: Env) -> Result<AuctionSettlement, DutchAuctionError> {
+        let auction = Self::load_auction(&env)?;
+        let settlement = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Settlement)
+            .ok_or(DutchAuctionError::AuctionNotStarted)?;
+        Self::extend_settlement_ttl(&env, &auction);
+        Ok(settlement)
+    }
+
+    /// Refresh the TTL of the auction record and the contract instance.
+    ///
+    /// Keeper entry point: anyone may call it to keep a long-running or
+    /// recently settled auction from being archived.
+    pub fn bump_entry(env: Env) -> Result<(), DutchAuctionError> {
+        Self::extend_instance_ttl(&env);
+        Self::load_auction(&env)?;
+        Ok(())
+    }
+
     // --- Internal helpers ---
 
     fn load_auction(env: &Env) -> Result<Auction, DutchAuctionError> {
-        env.storage()
+        let auction = env
+            .storage()
             .persistent()
             .get(&DataKey::Auction)
-            .ok_or(DutchAuctionError::AuctionNotStarted)
+            .ok_or(DutchAuctionError::AuctionNotStarted)?;
+        Self::extend_auction_ttl(env, &auction);
+        Ok(auction)
     }
 
-    fn compute_price(auction: &Auction, now: u64) -> Result<i128, DutchAuctionError> {
-        if now >= auction.end_time {
-            return Ok(auction.reserve_price);
-        }
+    fn save_auction(env: &Env, auction: &Auction) {
+        env.storage().persistent().set(&DataKey::Auction, auction);
+        Self::extend_auction_ttl(env, auction);
+    }
 
-        let elapsed = now - auction.start_time;
-        let total_duration = auction.end_time - auction.start_time;
+    fn extend_auction_ttl(env: &Env, auction: &Auction) {
+        Self::extend_record_ttl(env, &DataKey::Auction, auction);
+    }
+
+    fn extend_settlement_ttl(env: &Env, auction: &Auction) {
+        Self::extend_record_ttl(env, &DataKey::Settlement, auction);
+    }
+
+    fn extend_record_ttl(env: &Env, key: &DataKey, auction: &Auction) {
+        let seconds_left = auction.end_time.saturating_sub(env.ledger().timestamp());
+        let extend_to = u32::try_from(seconds_left / LEDGER_CLOSE_SECONDS)
+            .unwrap_or(u32::MAX)
+            .saturating_add(TTL_RETENTION)
+            .min(env.storage().max_ttl());
+        env.storage().persistent().extend_ttl(
+            key,
+            extend_to.saturating_sub(DAY_IN_LEDGERS),
+            extend_to,
+        );
+    }
+
+    fn extend_instance_ttl(env: &Env) {
+        env.storage()
+            .instance()
+            .extend_ttl(TTL_INSTANCE - DAY_IN_LEDGERS, TTL_INSTANCE);
+    }
+
+    /// Price a bid placed at `now` pays: the curve price, raised to the
+    /// settlement oracle's floor if one is set but never above `start_price`.
+    fn quote_price(env: &Env, auction: &Auction, now: u64) -> Result<i128, DutchAuctionError> {
+        let price = Self::compute_price(auction, now)?;
+        Ok(match &auction.settlement_oracle {
+            Some(oracle) => {
+                let floor: i128 =
+                    env.invoke_contract(oracle, &Symbol::new(env, "floor_price"), Vec::new(env));
+                price.max(floor).min(auction.start_price)
+            }
+            None => price,
+        })
+    }
 
-        if total_duration == 0 {
+    /// Price at `now`, always within `[reserve_price, start_price]`.
+    fn compute_price(auction: &Auction, now: u64) -> Result<i128, DutchAuctionError> {
+        if now >= auction.end_time {
             return Ok(auction.reserve_price);
         }
 
-        let total_decrement = auction.price_decrement * elapsed as i128;
-        let price = auction.start_price - total_decrement;
+        // Saturating arithmetic so an extreme decrement or elapsed time
+        // clamps to the reserve instead of overflowing.
+        let elapsed = now.saturating_sub(auction.start_time);
+        let total_decrement = auction.price_decrement.saturating_mul(elapsed as i128);
+        let price = auction.start_price.saturating_sub(total_decrement);
 
-        if price < auction.reserve_price {
-            Ok(auction.reserve_price)
-        } else {
-            Ok(price)
-        }
+        Ok(price.clamp(auction.reserve_price, auction.start_price))
     }
 }
+
+#[cfg(test)]
+mod test;
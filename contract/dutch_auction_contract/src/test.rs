@@ -0,0 +1,291 @@
+use crate::{DataKey, DutchAuctionContract, DutchAuctionContractClient, DutchAuctionError};
+use soroban_sdk::{
+    contract, contractimpl,
+    testutils::{storage::Persistent as _, Address as _, Ledger, MockAuth, MockAuthInvoke},
+    Address, Env, IntoVal, Symbol,
+};
+
+fn setup() -> (Env, DutchAuctionContractClient<'static>) {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(1_700_000_000);
+    let contract_id = env.register(DutchAuctionContract, ());
+    let client = DutchAuctionContractClient::new(&env, &contract_id);
+    (env, client)
+}
+
+fn auction_ttl(env: &Env, client: &DutchAuctionContractClient) -> u32 {
+    env.as_contract(&client.address, || {
+        env.storage().persistent().get_ttl(&DataKey::Auction)
+    })
+}
+
+// ---------------------------------------------------------------------------
+// Storage Expiry Tests
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_bumped_auction_survives_ledger_advancement() {
+    let (env, client) = setup();
+    let seller = Address::generate(&env);
+    client.create_auction(&seller, &1_000, &100, &1, &3_600, &false, &None);
+
+    // Advance to just short of the auction record's archival ledger.
+    let initial_ttl = auction_ttl(&env, &client);
+    env.ledger()
+        .with_mut(|li| li.sequence_number += initial_ttl - 300);
+    assert_eq!(auction_ttl(&env, &client), 300);
+
+    client.bump_entry();
+    assert_eq!(auction_ttl(&env, &client), initial_ttl);
+
+    // Past where the un-bumped record would have been archived, the
+    // auction is still live and readable.
+    env.ledger().with_mut(|li| li.sequence_number += 1_000);
+    assert_eq!(auction_ttl(&env, &client), initial_ttl - 1_000);
+    assert_eq!(client.get_auction().seller, seller);
+}
+
+// ---------------------------------------------------------------------------
+// Pricing Tests
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_price_holds_at_reserve_late_in_auction() {
+    let (env, client) = setup();
+    let seller = Address::generate(&env);
+    // Decays by 1 per second, so the curve crosses the reserve after 900s
+    // of a 3600s auction.
+    client.create_auction(&seller, &1_000, &100, &1, &3_600, &false, &None);
+    let start = env.ledger().timestamp();
+
+    env.ledger().set_timestamp(start + 899);
+    assert_eq!(client.get_current_price(), 101);
+
+    for elapsed in [900, 901, 1_800, 3_599, 3_600, 10_000] {
+        env.ledger().set_timestamp(start + elapsed);
+        assert_eq!(client.get_current_price(), 100);
+    }
+
+    env.ledger().set_timestamp(start + 3_000);
+    let bidder = Address::generate(&env);
+    client.place_bid(&bidder, &None);
+    assert_eq!(client.get_auction().current_price, 100);
+}
+
+#[test]
+fn test_price_stays_within_bounds() {
+    for decrement in [1, 7, 1_000, i128::MAX / 2, i128::MAX] {
+        let (env, client) = setup();
+        let seller = Address::generate(&env);
+        client.create_auction(&seller, &1_000, &100, &decrement, &3_600, &false, &None);
+        let start = env.ledger().timestamp();
+
+        for elapsed in (0..=4_000u64)
+            .step_by(37)
+            .chain([1, 3_599, u64::MAX - start])
+        {
+            env.ledger().set_timestamp(start + elapsed);
+            let price = client.get_current_price();
+            assert!(
+                (100..=1_000).contains(&price),
+                "price {} out of bounds at elapsed {} with decrement {}",
+                price,
+                elapsed,
+                decrement
+            );
+        }
+    }
+}
+
+#[test]
+fn test_overflowing_duration_rejected() {
+    let (env, client) = setup();
+    let seller = Address::generate(&env);
+    assert_eq!(
+        client.try_create_auction(&seller, &1_000, &100, &1, &u64::MAX, &false, &None),
+        Err(Ok(DutchAuctionError::InvalidBid))
+    );
+}
+
+// ---------------------------------------------------------------------------
+// Settlement Tests
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_settlement_records_auction_outcome() {
+    let (env, client) = setup();
+    let seller = Address::generate(&env);
+    client.create_auction(&seller, &1_000, &100, &1, &3_600, &false, &None);
+    let start = env.ledger().timestamp();
+
+    assert_eq!(
+        client.try_get_settlement(),
+        Err(Ok(DutchAuctionError::AuctionNotStarted))
+    );
+
+    // 250 seconds in, the price has decayed from 1000 to 750.
+    env.ledger().set_timestamp(start + 250);
+    let bidder = Address::generate(&env);
+    client.place_bid(&bidder, &None);
+
+    env.ledger().set_timestamp(start + 400);
+    client.settle_auction();
+
+    let settlement = client.get_settlement();
+    assert_eq!(settlement.winner, bidder);
+    assert_eq!(settlement.clearing_price, 750);
+    assert_eq!(settlement.settled_at, start + 400);
+}
+
+// ---------------------------------------------------------------------------
+// Seller Bid Tests
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_seller_bid_rejected_by_default() {
+    let (env, client) = setup();
+    let seller = Address::generate(&env);
+    client.create_auction(&seller, &1_000, &100, &1, &3_600, &false, &None);
+
+    assert_eq!(
+        client.try_place_bid(&seller, &None),
+        Err(Ok(DutchAuctionError::SellerBidNotAllowed))
+    );
+    assert_eq!(client.get_auction().winner, None);
+
+    let bidder = Address::generate(&env);
+    client.place_bid(&bidder, &None);
+    assert_eq!(client.get_auction().winner, Some(bidder));
+}
+
+#[test]
+fn test_seller_bid_allowed_when_enabled() {
+    let (env, client) = setup();
+    let seller = Address::generate(&env);
+    client.create_auction(&seller, &1_000, &100, &1, &3_600, &true, &None);
+
+    client.place_bid(&seller, &None);
+    assert_eq!(client.get_auction().winner, Some(seller));
+}
+
+#[test]
+fn test_bid_requires_bidder_authorization() {
+    let (env, client) = setup();
+    let seller = Address::generate(&env);
+    client.create_auction(&seller, &1_000, &100, &1, &3_600, &false, &None);
+    let bidder = Address::generate(&env);
+    let args = (&bidder, None::<i128>).into_val(&env);
+    let invoke = MockAuthInvoke {
+        contract: &client.address,
+        fn_name: "place_bid",
+        args,
+        sub_invokes: &[],
+    };
+
+    // The seller cannot place a bid in another address's name.
+    assert!(client
+        .mock_auths(&[MockAuth {
+            address: &seller,
+            invoke: &invoke,
+        }])
+        .try_place_bid(&bidder, &None)
+        .is_err());
+    assert_eq!(client.get_auction().winner, None);
+
+    client
+        .mock_auths(&[MockAuth {
+            address: &bidder,
+            invoke: &invoke,
+        }])
+        .place_bid(&bidder, &None);
+    assert_eq!(client.get_auction().winner, Some(bidder));
+}
+
+#[contract]
+struct MockFloorOracle;
+
+#[contractimpl]
+impl MockFloorOracle {
+    pub fn set_floor(env: Env, floor: i128) {
+        env.storage()
+            .instance()
+            .set(&Symbol::new(&env, "floor"), &floor);
+    }
+
+    pub fn floor_price(env: Env) -> i128 {
+        env.storage()
+            .instance()
+            .get(&Symbol::new(&env, "floor"))
+            .unwrap_or(0)
+    }
+}
+
+fn floor_oracle(env: &Env, floor: i128) -> Address {
+    let oracle = env.register(MockFloorOracle, ());
+    MockFloorOracleClient::new(env, &oracle).set_floor(&floor);
+    oracle
+}
+
+#[test]
+fn test_settlement_oracle_raises_clearing_price() {
+    let (env, client) = setup();
+    let oracle = floor_oracle(&env, 900);
+    let seller = Address::generate(&env);
+    client.create_auction(&seller, &1_000, &100, &1, &3_600, &false, &Some(oracle));
+    let start = env.ledger().timestamp();
+
+    // The curve has decayed to 750, below the oracle's floor of 900.
+    env.ledger().set_timestamp(start + 250);
+    assert_eq!(client.get_current_price(), 900);
+    let bidder = Address::generate(&env);
+    assert_eq!(
+        client.try_place_bid(&bidder, &Some(750)),
+        Err(Ok(DutchAuctionError::PriceAboveLimit))
+    );
+
+    client.place_bid(&bidder, &Some(900));
+    assert_eq!(client.get_auction().current_price, 900);
+
+    client.settle_auction();
+    assert_eq!(client.get_settlement().clearing_price, 900);
+}
+
+#[test]
+fn test_settlement_oracle_floor_capped_at_start_price() {
+    let (env, client) = setup();
+    let oracle = floor_oracle(&env, 5_000);
+    let seller = Address::generate(&env);
+    client.create_auction(&seller, &1_000, &100, &1, &3_600, &false, &Some(oracle));
+    let start = env.ledger().timestamp();
+
+    env.ledger().set_timestamp(start + 250);
+    assert_eq!(client.get_current_price(), 1_000);
+    let bidder = Address::generate(&env);
+    client.place_bid(&bidder, &Some(1_000));
+    assert_eq!(client.get_auction().current_price, 1_000);
+}
+
+// ---------------------------------------------------------------------------
+// Price Protection Tests
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_bid_respects_max_acceptable_price() {
+    let (env, client) = setup();
+    let seller = Address::generate(&env);
+    client.create_auction(&seller, &1_000, &100, &1, &3_600, &false, &None);
+    let start = env.ledger().timestamp();
+
+    env.ledger().set_timestamp(start + 250);
+    let bidder = Address::generate(&env);
+    assert_eq!(
+        client.try_place_bid(&bidder, &Some(749)),
+        Err(Ok(DutchAuctionError::PriceAboveLimit))
+    );
+    assert_eq!(client.get_auction().winner, None);
+
+    client.place_bid(&bidder, &Some(750));
+    assert_eq!(client.get_auction().winner, Some(bidder));
+    assert_eq!(client.get_auction().current_price, 750);
+}
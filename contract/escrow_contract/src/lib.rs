@@ -13,8 +13,8 @@
 //! - Integration with ticket contract for event-based escrows
 
 use soroban_sdk::{
-    contract, contracterror, contractimpl, contracttype, token::TokenClient, Address, Env, String,
-    Symbol, Vec,
+    contract, contracterror, contractimpl, contracttype, token::TokenClient, vec, xdr::ToXdr,
+    Address, Bytes, BytesN, Env, IntoVal, String, Symbol, Vec,
 };
 
 /// Errors that can occur during escrow operations
@@ -39,6 +39,12 @@ pub enum EscrowError {
     InvalidResolution = 8,
     /// Escrow expired
     EscrowExpired = 9,
+    /// Depositor already has the maximum number of open escrows
+    EscrowLimitReached = 10,
+    /// Release condition attestation did not return true
+    ConditionNotMet = 11,
+    /// Payouts are paused by the circuit breaker
+    ContractPaused = 12,
     /// Functionality not implemented yet
     NotImplemented = 255,
 }
@@ -81,6 +87,22 @@ pub struct Escrow {
     pub required_confirmations: u32,
     /// Current confirmations (addresses of those who confirmed)
     pub confirmations: Vec<Address>,
+    /// Depositor's external order reference
+    pub reference: Option<BytesN<32>>,
+    /// Free-form note from the depositor
+    pub memo: String,
+    /// Account that receives released funds instead of the beneficiary
+    pub payout_address: Option<Address>,
+}
+
+/// External attestation an escrow's release is gated on
+#[contracttype]
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ReleaseCondition {
+    /// Attesting contract
+    pub contract: Address,
+    /// Function called with the escrow ID, returning a bool
+    pub func: Symbol,
 }
 
 /// Dispute data structure
@@ -99,6 +121,71 @@ pub struct Dispute {
     pub resolved: bool,
     /// Resolution details
     pub resolution: Option<String>,
+    /// Bond posted by the initiator, returned if they win the dispute
+    pub bond: u128,
+}
+
+/// Durable proof that an escrow was refunded to its depositor
+#[contracttype]
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct RefundReceipt {
+    /// Refunded escrow
+    pub escrow_id: Symbol,
+    /// Dispute whose resolution ordered the refund, if any
+    pub dispute_id: Option<Symbol>,
+    /// Depositor the funds were returned to
+    pub depositor: Address,
+    /// Amount refunded
+    pub amount: u128,
+    /// Token the refund was paid in
+    pub token: Address,
+    /// Ledger timestamp of the refund
+    pub refunded_at: u64,
+    /// Hash of the escrow, amount, timestamp and ledger sequence, unique to
+    /// this refund
+    pub refund_id: BytesN<32>,
+}
+
+/// Record of a privileged call, kept in the admin audit log
+#[contracttype]
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct AdminAction {
+    /// Name of the admin operation
+    pub action: Symbol,
+    /// Address that performed it
+    pub actor: Address,
+    /// Ledger timestamp of the call
+    pub timestamp: u64,
+    /// SHA-256 of the XDR-encoded call arguments
+    pub data_hash: BytesN<32>,
+}
+
+/// Outflow limit that pauses payouts when exceeded
+#[contracttype]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CircuitBreaker {
+    /// Maximum amount paid out within one window
+    pub max_outflow_per_window: u128,
+    /// Window length in seconds
+    pub outflow_window: u64,
+}
+
+/// Payouts made in the current circuit breaker window
+#[contracttype]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OutflowWindow {
+    /// Timestamp the current window started at
+    pub window_start: u64,
+    /// Amount paid out since `window_start`
+    pub outflow: u128,
+}
+
+/// Kinds of persistent records that can be refreshed via `bump_entry`
+#[contracttype]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EntryKind {
+    Escrow,
+    Dispute,
 }
 
 /// Storage keys
@@ -109,14 +196,47 @@ enum DataKey {
     EscrowCounter,   // u32 counter for generating unique IDs
     Escrow(Symbol),  // Escrow data keyed by escrow_id
     Dispute(Symbol), // Dispute data keyed by dispute_id
+    TotalLocked,     // u128 sum of amounts held for funded escrows
+    // Escrow ID created by a depositor's request, keyed by its idempotency key
+    IdempotencyKey(Address, BytesN<32>),
+    MaxOpenEscrows,       // u32 cap on open escrows per depositor, 0 = unlimited
+    OpenEscrows(Address), // u32 number of a depositor's escrows not yet settled
+    DisputeBond,          // u128 bond a party must post to open a dispute
+    EscrowsByStatus(EscrowStatus), // u32 number of escrows currently in a status
+    // Escrow ID at a position in a status index
+    EscrowByStatusIndex(EscrowStatus, u32),
+    StatusIndexPosition(Symbol), // u32 position of an escrow in its status index
+    Reference(BytesN<32>),       // Escrow ID tagged with an external reference
+    // Marks an address allowed to confirm releases for an escrow's depositor
+    ReleaseDelegate(Symbol, Address),
+    AdminLogCount,  // u32 number of admin actions ever logged
+    AdminLog(u32),  // AdminAction in ring-buffer slot (count % ADMIN_LOG_SIZE)
+    CircuitBreaker, // CircuitBreaker limits on payouts
+    Outflow,        // OutflowWindow for the current window
+    Paused,         // bool set when the circuit breaker trips
+    // RefundReceipt keyed by escrow_id
+    RefundReceipt(Symbol),
+    // ReleaseCondition gating an escrow's release, keyed by escrow_id
+    ReleaseCondition(Symbol),
 }
 
+const DAY_IN_LEDGERS: u32 = 17280;
+const LEDGER_CLOSE_SECONDS: u64 = 5;
+const TTL_INSTANCE: u32 = DAY_IN_LEDGERS * 30; // 30 days
+const TTL_RETENTION: u32 = DAY_IN_LEDGERS * 30; // 30 days past expiry
+const TTL_PERSISTENT: u32 = DAY_IN_LEDGERS * 90; // 90 days
+
+/// Number of most recent admin actions kept in the audit log.
+const ADMIN_LOG_SIZE: u32 = 100;
+
 /// Main contract implementation
 #[contract]
 pub struct EscrowContract;
 
 #[cfg(test)]
 mod security_tests;
+#[cfg(test)]
+mod test;
 
 #[contractimpl]
 impl EscrowContract {
@@ -129,6 +249,7 @@ impl EscrowContract {
         env.storage().instance().set(&DataKey::Token, &token);
         env.storage().instance().set(&DataKey::Admin, &admin);
         env.storage().instance().set(&DataKey::EscrowCounter, &0u32);
+        Self::extend_instance_ttl(&env);
         Ok(())
     }
 
@@ -136,23 +257,31 @@ impl EscrowContract {
     ///
     /// # Arguments
     ///
+    /// * `depositor` - Address funding the escrow; must authorize the call
     /// * `beneficiary` - Address of the beneficiary
     /// * `amount` - Amount to escrow
     /// * `expires_at` - Expiration timestamp
     /// * `terms` - Escrow terms and conditions
     /// * `required_confirmations` - Number of confirmations needed for release
+    /// * `idempotency_key` - Client-chosen key identifying this request; a
+    ///   retry with a key the depositor already used is rejected
     ///
     /// # Returns
     ///
     /// Escrow ID of the newly created escrow
+    #[allow(clippy::too_many_arguments)]
     pub fn create_escrow(
         env: Env,
+        depositor: Address,
         beneficiary: Address,
         amount: u128,
         expires_at: u64,
         terms: String,
         required_confirmations: u32,
+        idempotency_key: BytesN<32>,
     ) -> Result<Symbol, EscrowError> {
+        depositor.require_auth();
+
         // Validate inputs
         if amount == 0 {
             return Err(EscrowError::InvalidTerms);
@@ -164,14 +293,28 @@ impl EscrowContract {
             return Err(EscrowError::InvalidTerms);
         }
 
-        let depositor = env.invoker();
+        // Reject retries of a request that already created an escrow
+        let idempotency_key = DataKey::IdempotencyKey(depositor.clone(), idempotency_key);
+        if env.storage().persistent().has(&idempotency_key) {
+            return Err(EscrowError::EscrowAlreadyExists);
+        }
+
+        // Bound the number of unsettled escrows a single depositor can hold
+        let max_open: u32 = env
+            .storage()
+            .instance()
+            .get(&DataKey::MaxOpenEscrows)
+            .unwrap_or(0);
+        if max_open > 0 && Self::get_open_escrow_count(env.clone(), depositor.clone()) >= max_open {
+            return Err(EscrowError::EscrowLimitReached);
+        }
 
         // Generate unique escrow ID
         let counter_key = DataKey::EscrowCounter;
         let mut counter: u32 = env.storage().instance().get(&counter_key).unwrap_or(0);
         counter += 1;
         env.storage().instance().set(&counter_key, &counter);
-        let escrow_id = Symbol::new(&env, &format!("ESCROW-{}", counter));
+        let escrow_id = Symbol::new(&env, &format!("ESCROW_{}", counter));
 
         // Create escrow record
         let escrow = Escrow {
@@ -185,12 +328,18 @@ impl EscrowContract {
             terms: terms.clone(),
             required_confirmations,
             confirmations: Vec::new(&env),
+            reference: None,
+            memo: String::from_str(&env, ""),
+            payout_address: None,
         };
 
         // Store escrow
-        env.storage()
-            .instance()
-            .set(&DataKey::Escrow(escrow_id.clone()), &escrow);
+        Self::save_escrow(&env, &escrow);
+        Self::index_status(&env, &escrow_id, EscrowStatus::Pending, true);
+        env.storage().persistent().set(&idempotency_key, &escrow_id);
+        Self::extend_record_ttl(&env, &idempotency_key, expires_at);
+        Self::track_open_escrow(&env, &depositor, true);
+        Self::extend_instance_ttl(&env);
 
         Ok(escrow_id)
     }
@@ -205,21 +354,17 @@ impl EscrowContract {
     ///
     /// True if funding was successful
     pub fn fund_escrow(env: Env, escrow_id: Symbol) -> Result<bool, EscrowError> {
-        let invoker = env.invoker();
+        Self::extend_instance_ttl(&env);
 
         // Load escrow
         let mut escrow = Self::get_escrow_internal(&env, &escrow_id)?;
+        escrow.depositor.require_auth();
 
-        // Check status and authorization
+        // Check status
         if escrow.status != EscrowStatus::Pending {
             return Err(EscrowError::AlreadyCompleted);
         }
-        if invoker != escrow.depositor {
-            return Err(EscrowError::Unauthorized);
-        }
         if env.ledger().timestamp() >= escrow.expires_at {
-            escrow.status = EscrowStatus::Expired;
-            Self::save_escrow(&env, &escrow);
             return Err(EscrowError::EscrowExpired);
         }
 
@@ -230,29 +375,200 @@ impl EscrowContract {
             .amount
             .try_into()
             .map_err(|_| EscrowError::InsufficientFunds)?;
-        token.transfer_from(&invoker, &env.current_contract_address(), &amount_i128);
+        token.transfer(
+            &escrow.depositor,
+            env.current_contract_address(),
+            &amount_i128,
+        );
+        Self::track_locked(&env, escrow.amount, true)?;
 
         // Update escrow status
-        escrow.status = EscrowStatus::Funded;
+        Self::set_status(&env, &mut escrow, EscrowStatus::Funded);
         Self::save_escrow(&env, &escrow);
 
         Ok(true)
     }
 
+    /// Gate an escrow's release on an external attestation
+    ///
+    /// Only the depositor may set the condition, and only before funding.
+    /// Once confirmations are met, `release_funds` calls `function` on
+    /// `contract` with the escrow ID and releases only if it returns true.
+    ///
+    /// # Arguments
+    ///
+    /// * `escrow_id` - Identifier for the escrow
+    /// * `contract` - Address of the attesting contract
+    /// * `function` - Attestation function, taking the escrow ID and returning a bool
+    pub fn set_release_condition(
+        env: Env,
+        escrow_id: Symbol,
+        contract: Address,
+        function: Symbol,
+    ) -> Result<(), EscrowError> {
+        Self::extend_instance_ttl(&env);
+
+        let escrow = Self::get_escrow_internal(&env, &escrow_id)?;
+        escrow.depositor.require_auth();
+        if escrow.status != EscrowStatus::Pending {
+            return Err(EscrowError::AlreadyCompleted);
+        }
+
+        let key = DataKey::ReleaseCondition(escrow_id);
+        env.storage().persistent().set(
+            &key,
+            &ReleaseCondition {
+                contract,
+                func: function,
+            },
+        );
+        Self::extend_record_ttl(&env, &key, escrow.expires_at);
+
+        Ok(())
+    }
+
+    /// Get the attestation an escrow's release is gated on, if any
+    ///
+    /// # Arguments
+    ///
+    /// * `escrow_id` - Identifier for the escrow
+    pub fn get_release_condition(env: Env, escrow_id: Symbol) -> Option<ReleaseCondition> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::ReleaseCondition(escrow_id))
+    }
+
+    /// Tag an escrow with an external reference and memo
+    ///
+    /// Only the depositor may tag an escrow, and each reference may only
+    /// point at one escrow. Lets an organizer resolve their own order ID
+    /// to an escrow with `get_escrow_by_reference`.
+    ///
+    /// # Arguments
+    ///
+    /// * `escrow_id` - Identifier for the escrow
+    /// * `reference` - External reference, e.g. a hash of an order ID
+    /// * `memo` - Free-form note stored on the escrow
+    pub fn set_reference(
+        env: Env,
+        escrow_id: Symbol,
+        reference: BytesN<32>,
+        memo: String,
+    ) -> Result<(), EscrowError> {
+        Self::extend_instance_ttl(&env);
+
+        let mut escrow = Self::get_escrow_internal(&env, &escrow_id)?;
+        escrow.depositor.require_auth();
+
+        let reference_key = DataKey::Reference(reference.clone());
+        if env.storage().persistent().has(&reference_key) {
+            return Err(EscrowError::EscrowAlreadyExists);
+        }
+        if let Some(previous) = escrow.reference.clone() {
+            env.storage()
+                .persistent()
+                .remove(&DataKey::Reference(previous));
+        }
+
+        env.storage().persistent().set(&reference_key, &escrow_id);
+        Self::extend_record_ttl(&env, &reference_key, escrow.expires_at);
+        escrow.reference = Some(reference);
+        escrow.memo = memo;
+        Self::save_escrow(&env, &escrow);
+
+        Ok(())
+    }
+
+    /// Route released funds to an account other than the beneficiary
+    ///
+    /// Only the beneficiary may set it. The beneficiary remains the party
+    /// that confirms releases and disputes; only the receiving account changes.
+    ///
+    /// # Arguments
+    ///
+    /// * `escrow_id` - Identifier for the escrow
+    /// * `payout_address` - Account to pay, or None to pay the beneficiary
+    pub fn set_payout_address(
+        env: Env,
+        escrow_id: Symbol,
+        payout_address: Option<Address>,
+    ) -> Result<(), EscrowError> {
+        Self::extend_instance_ttl(&env);
+
+        let mut escrow = Self::get_escrow_internal(&env, &escrow_id)?;
+        escrow.beneficiary.require_auth();
+        if escrow.status == EscrowStatus::Completed
+            || escrow.status == EscrowStatus::Refunded
+            || escrow.status == EscrowStatus::Expired
+        {
+            return Err(EscrowError::AlreadyCompleted);
+        }
+
+        escrow.payout_address = payout_address;
+        Self::save_escrow(&env, &escrow);
+
+        Ok(())
+    }
+
+    /// Allow another address to confirm releases on the depositor's behalf
+    ///
+    /// Only the depositor may add delegates. Delegates can call
+    /// `release_funds` but cannot open disputes.
+    ///
+    /// # Arguments
+    ///
+    /// * `escrow_id` - Identifier for the escrow
+    /// * `delegate` - Address to delegate release authority to
+    pub fn add_release_delegate(
+        env: Env,
+        escrow_id: Symbol,
+        delegate: Address,
+    ) -> Result<(), EscrowError> {
+        Self::set_release_delegate(env, escrow_id, delegate, true)
+    }
+
+    /// Revoke a release delegate added with `add_release_delegate`
+    ///
+    /// # Arguments
+    ///
+    /// * `escrow_id` - Identifier for the escrow
+    /// * `delegate` - Delegate to revoke
+    pub fn remove_release_delegate(
+        env: Env,
+        escrow_id: Symbol,
+        delegate: Address,
+    ) -> Result<(), EscrowError> {
+        Self::set_release_delegate(env, escrow_id, delegate, false)
+    }
+
+    /// Check whether an address may confirm releases for an escrow's depositor
+    pub fn is_release_delegate(env: Env, escrow_id: Symbol, delegate: Address) -> bool {
+        env.storage()
+            .persistent()
+            .has(&DataKey::ReleaseDelegate(escrow_id, delegate))
+    }
+
     /// Release funds from escrow
     ///
-    /// Each call adds the invoker's confirmation. When the required number
+    /// Each call adds the caller's confirmation. When the required number
     /// of confirmations is reached, funds are transferred to the beneficiary.
     ///
     /// # Arguments
     ///
     /// * `escrow_id` - Identifier for the escrow
+    /// * `caller` - Depositor, beneficiary or release delegate confirming
     ///
     /// # Returns
     ///
     /// True if release was successful
-    pub fn release_funds(env: Env, escrow_id: Symbol) -> Result<bool, EscrowError> {
-        let invoker = env.invoker();
+    pub fn release_funds(
+        env: Env,
+        escrow_id: Symbol,
+        caller: Address,
+    ) -> Result<bool, EscrowError> {
+        caller.require_auth();
+        Self::extend_instance_ttl(&env);
+        Self::require_not_paused(&env)?;
 
         // Load escrow
         let mut escrow = Self::get_escrow_internal(&env, &escrow_id)?;
@@ -262,33 +578,48 @@ impl EscrowContract {
             return Err(EscrowError::AlreadyCompleted);
         }
 
-        // Only depositor or beneficiary may confirm
-        if invoker != escrow.depositor && invoker != escrow.beneficiary {
+        // Only depositor or beneficiary may confirm; a release delegate
+        // confirms on the depositor's behalf
+        let confirmer = if caller == escrow.depositor || caller == escrow.beneficiary {
+            caller
+        } else if Self::is_release_delegate(env.clone(), escrow_id.clone(), caller) {
+            escrow.depositor.clone()
+        } else {
             return Err(EscrowError::Unauthorized);
-        }
+        };
 
         // Check expiration
         if env.ledger().timestamp() >= escrow.expires_at {
-            escrow.status = EscrowStatus::Expired;
-            Self::save_escrow(&env, &escrow);
             return Err(EscrowError::EscrowExpired);
         }
 
-        // Add invoker's confirmation if not already present
+        // Add the confirmation if not already present
         let mut confirmations = escrow.confirmations.clone();
-        if !confirmations.contains(&invoker) {
-            confirmations.push_back(invoker.clone());
+        if !confirmations.contains(&confirmer) {
+            confirmations.push_back(confirmer.clone());
             escrow.confirmations = confirmations.clone();
         }
 
         // Check if required confirmations are met
-        if confirmations.len() < escrow.required_confirmations as usize {
+        if confirmations.len() < escrow.required_confirmations {
             // Not enough confirmations yet; save updated confirmations and return false
             Self::save_escrow(&env, &escrow);
             return Ok(false);
         }
 
-        // Enough confirmations: transfer funds to beneficiary
+        // Enough confirmations: the release condition, if any, must attest
+        if let Some(condition) = Self::get_release_condition(env.clone(), escrow_id.clone()) {
+            let attested: bool = env.invoke_contract(
+                &condition.contract,
+                &condition.func,
+                vec![&env, escrow_id.into_val(&env)],
+            );
+            if !attested {
+                return Err(EscrowError::ConditionNotMet);
+            }
+        }
+
+        // Transfer funds to beneficiary
         let token_addr: Address = env.storage().instance().get(&DataKey::Token).unwrap();
         let token = TokenClient::new(&env, &token_addr);
         let amount_i128 = escrow
@@ -297,13 +628,54 @@ impl EscrowContract {
             .map_err(|_| EscrowError::InsufficientFunds)?;
         token.transfer(
             &env.current_contract_address(),
-            &escrow.beneficiary,
+            Self::payout_recipient(&escrow),
             &amount_i128,
         );
+        Self::track_locked(&env, escrow.amount, false)?;
+        Self::record_outflow(&env, escrow.amount);
 
         // Update escrow status
-        escrow.status = EscrowStatus::Completed;
+        Self::set_status(&env, &mut escrow, EscrowStatus::Completed);
         Self::save_escrow(&env, &escrow);
+        Self::track_open_escrow(&env, &escrow.depositor, false);
+
+        Ok(true)
+    }
+
+    /// Close out an escrow that reached its expiry unsettled
+    ///
+    /// Keeper entry point: anyone may call it once `expires_at` has passed.
+    /// A funded escrow's amount is returned to the depositor. Either way
+    /// the escrow is marked expired and stops counting towards the
+    /// depositor's open escrows. Disputed escrows are left for the admin
+    /// to resolve.
+    ///
+    /// # Arguments
+    ///
+    /// * `escrow_id` - Identifier for the escrow
+    ///
+    /// # Returns
+    ///
+    /// True if the escrow was expired
+    pub fn expire_escrow(env: Env, escrow_id: Symbol) -> Result<bool, EscrowError> {
+        Self::extend_instance_ttl(&env);
+        let mut escrow = Self::get_escrow_internal(&env, &escrow_id)?;
+
+        if escrow.status != EscrowStatus::Pending && escrow.status != EscrowStatus::Funded {
+            return Err(EscrowError::AlreadyCompleted);
+        }
+        if env.ledger().timestamp() < escrow.expires_at {
+            return Err(EscrowError::InvalidTerms);
+        }
+
+        if escrow.status == EscrowStatus::Funded {
+            Self::require_not_paused(&env)?;
+            Self::refund_depositor(&env, &escrow, None)?;
+        }
+
+        Self::set_status(&env, &mut escrow, EscrowStatus::Expired);
+        Self::save_escrow(&env, &escrow);
+        Self::track_open_escrow(&env, &escrow.depositor, false);
 
         Ok(true)
     }
@@ -313,6 +685,7 @@ impl EscrowContract {
     /// # Arguments
     ///
     /// * `escrow_id` - Identifier for the escrow
+    /// * `initiator` - Depositor or beneficiary opening the dispute
     /// * `reason` - Dispute reason
     ///
     /// # Returns
@@ -321,47 +694,68 @@ impl EscrowContract {
     pub fn create_dispute(
         env: Env,
         escrow_id: Symbol,
+        initiator: Address,
         reason: String,
     ) -> Result<Symbol, EscrowError> {
-        let invoker = env.invoker();
+        initiator.require_auth();
+        Self::extend_instance_ttl(&env);
 
         // Load escrow
         let mut escrow = Self::get_escrow_internal(&env, &escrow_id)?;
 
         // Only depositor or beneficiary can dispute
-        if invoker != escrow.depositor && invoker != escrow.beneficiary {
+        if initiator != escrow.depositor && initiator != escrow.beneficiary {
             return Err(EscrowError::Unauthorized);
         }
 
-        // Only pending or funded escrows can be disputed
-        if escrow.status != EscrowStatus::Pending && escrow.status != EscrowStatus::Funded {
+        // Only funded escrows can be disputed: resolution pays out the
+        // escrow amount, which must already be held by the contract
+        if escrow.status == EscrowStatus::Pending {
+            return Err(EscrowError::InsufficientFunds);
+        }
+        if escrow.status != EscrowStatus::Funded {
             return Err(EscrowError::AlreadyCompleted);
         }
 
-        // Check if dispute already exists (we'll check by trying to load dispute with same escrow_id)
-        // We store disputes keyed by escrow_id (unique per escrow)
-        let dispute_key = DataKey::Dispute(escrow_id.clone());
-        if env.storage().instance().has(&dispute_key) {
+        // Dispute IDs are derived from the escrow ID, so an escrow can only
+        // ever have one dispute
+        let dispute_id = Self::dispute_id_for(&env, &escrow_id);
+        let dispute_key = DataKey::Dispute(dispute_id.clone());
+        if env.storage().persistent().has(&dispute_key) {
             return Err(EscrowError::DisputeExists);
         }
 
-        // Generate dispute ID
-        let dispute_id = Symbol::new(&env, &format!("DISPUTE-{}", escrow_id.to_string()));
+        // Take the dispute bond from the initiator
+        let bond = Self::get_dispute_bond(env.clone());
+        if bond > 0 {
+            let token_addr: Address = env.storage().instance().get(&DataKey::Token).unwrap();
+            let bond_i128: i128 = bond
+                .try_into()
+                .map_err(|_| EscrowError::InsufficientFunds)?;
+            TokenClient::new(&env, &token_addr).transfer(
+                &initiator,
+                env.current_contract_address(),
+                &bond_i128,
+            );
+            Self::track_locked(&env, bond, true)?;
+        }
 
         let dispute = Dispute {
             dispute_id: dispute_id.clone(),
             escrow_id: escrow_id.clone(),
-            initiator: invoker,
+            initiator,
             reason: reason.clone(),
             resolved: false,
             resolution: None,
+            bond,
         };
 
         // Store dispute
-        env.storage().instance().set(&dispute_key, &dispute);
+        env.storage().persistent().set(&dispute_key, &dispute);
+        Self::extend_record_ttl(&env, &dispute_key, escrow.expires_at);
 
         // Update escrow status
-        escrow.status = EscrowStatus::Disputed;
+        Self::set_status(&env, &mut escrow, EscrowStatus::Disputed);
         Self::save_escrow(&env, &escrow);
 
         Ok(dispute_id)
@@ -383,19 +777,14 @@ impl EscrowContract {
         resolution: String,
     ) -> Result<bool, EscrowError> {
         let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
-        if env.invoker() != admin {
-            return Err(EscrowError::Unauthorized);
-        }
+        admin.require_auth();
+        Self::extend_instance_ttl(&env);
+        Self::require_not_paused(&env)?;
 
-        // Load dispute using its ID to find the associated escrow
-        // We need a way to get dispute by ID. Since we store disputes by escrow_id,
-        // we would need to scan or maintain a mapping. Simpler: we can require the
-        // caller to provide escrow_id as well, but the signature only has dispute_id.
-        // We'll store disputes in a map keyed by dispute_id as well.
         let dispute_key = DataKey::Dispute(dispute_id.clone());
         let mut dispute: Dispute = env
             .storage()
-            .instance()
+            .persistent()
             .get(&dispute_key)
             .ok_or(EscrowError::EscrowNotFound)?;
 
@@ -403,45 +792,80 @@ impl EscrowContract {
             return Err(EscrowError::AlreadyCompleted);
         }
 
-        // Load escrow
+        // Load escrow. Disputes are only opened on funded escrows, so a
+        // disputed escrow's amount is held by the contract
         let mut escrow = Self::get_escrow_internal(&env, &dispute.escrow_id)?;
+        if escrow.status != EscrowStatus::Disputed {
+            return Err(EscrowError::AlreadyCompleted);
+        }
 
         // Check resolution string
-        if resolution != "release" && resolution != "refund" {
+        let release = String::from_str(&env, "release");
+        if resolution != release && resolution != String::from_str(&env, "refund") {
             return Err(EscrowError::InvalidResolution);
         }
 
         // Resolve according to resolution
         let token_addr: Address = env.storage().instance().get(&DataKey::Token).unwrap();
         let token = TokenClient::new(&env, &token_addr);
-        let amount_i128 = escrow
-            .amount
-            .try_into()
-            .map_err(|_| EscrowError::InsufficientFunds)?;
 
-        if resolution == "release" {
+        if resolution == release {
             // Release to beneficiary
+            let amount_i128: i128 = escrow
+                .amount
+                .try_into()
+                .map_err(|_| EscrowError::InsufficientFunds)?;
             token.transfer(
                 &env.current_contract_address(),
-                &escrow.beneficiary,
+                Self::payout_recipient(&escrow),
                 &amount_i128,
             );
-            escrow.status = EscrowStatus::Completed;
+            Self::track_locked(&env, escrow.amount, false)?;
+            Self::record_outflow(&env, escrow.amount);
+            Self::set_status(&env, &mut escrow, EscrowStatus::Completed);
         } else {
             // refund
-            token.transfer(
-                &env.current_contract_address(),
-                &escrow.depositor,
-                &amount_i128,
-            );
-            escrow.status = EscrowStatus::Refunded;
+            Self::refund_depositor(&env, &escrow, Some(dispute_id.clone()))?;
+            Self::set_status(&env, &mut escrow, EscrowStatus::Refunded);
+        }
+
+        // Return the bond to a winning initiator, otherwise forfeit it to
+        // the other party
+        if dispute.bond > 0 {
+            let bond_i128: i128 = dispute
+                .bond
+                .try_into()
+                .map_err(|_| EscrowError::InsufficientFunds)?;
+            let initiator_won = if dispute.initiator == escrow.depositor {
+                escrow.status == EscrowStatus::Refunded
+            } else {
+                escrow.status == EscrowStatus::Completed
+            };
+            let bond_recipient = if initiator_won {
+                dispute.initiator.clone()
+            } else if dispute.initiator == escrow.depositor {
+                escrow.beneficiary.clone()
+            } else {
+                escrow.depositor.clone()
+            };
+            token.transfer(&env.current_contract_address(), &bond_recipient, &bond_i128);
+            Self::track_locked(&env, dispute.bond, false)?;
         }
 
         // Update dispute and escrow
         dispute.resolved = true;
         dispute.resolution = Some(resolution.clone());
-        env.storage().instance().set(&dispute_key, &dispute);
+        env.storage().persistent().set(&dispute_key, &dispute);
+        Self::extend_record_ttl(&env, &dispute_key, escrow.expires_at);
         Self::save_escrow(&env, &escrow);
+        Self::track_open_escrow(&env, &escrow.depositor, false);
+
+        Self::log_admin_action(
+            &env,
+            &admin,
+            "resolve_dispute",
+            (dispute_id, resolution).to_xdr(&env),
+        );
 
         Ok(true)
     }
@@ -459,18 +883,551 @@ impl EscrowContract {
         Self::get_escrow_internal(&env, &escrow_id)
     }
 
+    /// Set the maximum number of open escrows a single depositor may hold
+    ///
+    /// Only the admin may call this. Escrows count as open from creation
+    /// until they are completed, refunded or expired with `expire_escrow`.
+    ///
+    /// # Arguments
+    ///
+    /// * `max_open` - New cap; 0 removes the limit
+    pub fn set_max_open_escrows(env: Env, max_open: u32) -> Result<(), EscrowError> {
+        let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+        env.storage()
+            .instance()
+            .set(&DataKey::MaxOpenEscrows, &max_open);
+        Self::extend_instance_ttl(&env);
+        Self::log_admin_action(&env, &admin, "set_max_open", max_open.to_xdr(&env));
+        Ok(())
+    }
+
+    /// Set the bond a party must post to open a dispute
+    ///
+    /// Only the admin may call this. Disputes already open keep the bond
+    /// they were created with.
+    ///
+    /// # Arguments
+    ///
+    /// * `bond` - New bond amount; 0 makes disputes free
+    pub fn set_dispute_bond(env: Env, bond: u128) -> Result<(), EscrowError> {
+        let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+        env.storage().instance().set(&DataKey::DisputeBond, &bond);
+        Self::extend_instance_ttl(&env);
+        Self::log_admin_action(&env, &admin, "set_dispute_bond", bond.to_xdr(&env));
+        Ok(())
+    }
+
+    /// Configure the payout circuit breaker
+    ///
+    /// Only the admin may call this. Once releases and refunds within one
+    /// window add up to more than `max_outflow_per_window`, payouts pause
+    /// until the admin calls `unpause`.
+    ///
+    /// # Arguments
+    ///
+    /// * `max_outflow_per_window` - Payout limit per window; 0 disables the breaker
+    /// * `outflow_window` - Window length in seconds
+    pub fn set_circuit_breaker(
+        env: Env,
+        max_outflow_per_window: u128,
+        outflow_window: u64,
+    ) -> Result<(), EscrowError> {
+        let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+        if max_outflow_per_window > 0 && outflow_window == 0 {
+            return Err(EscrowError::InvalidTerms);
+        }
+        let breaker = CircuitBreaker {
+            max_outflow_per_window,
+            outflow_window,
+        };
+        env.storage()
+            .instance()
+            .set(&DataKey::CircuitBreaker, &breaker);
+        Self::extend_instance_ttl(&env);
+        Self::log_admin_action(&env, &admin, "set_circuit_breaker", breaker.to_xdr(&env));
+        Ok(())
+    }
+
+    /// Resume payouts after the circuit breaker has tripped
+    ///
+    /// Only the admin may call this. Starts a fresh outflow window.
+    pub fn unpause(env: Env) -> Result<(), EscrowError> {
+        let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+        env.storage().instance().remove(&DataKey::Paused);
+        env.storage().instance().remove(&DataKey::Outflow);
+        Self::extend_instance_ttl(&env);
+        Self::log_admin_action(&env, &admin, "unpause", Bytes::new(&env));
+        Ok(())
+    }
+
+    /// Check whether payouts are paused by the circuit breaker
+    pub fn is_paused(env: Env) -> bool {
+        env.storage()
+            .instance()
+            .get(&DataKey::Paused)
+            .unwrap_or(false)
+    }
+
+    /// Get the bond currently required to open a dispute
+    pub fn get_dispute_bond(env: Env) -> u128 {
+        env.storage()
+            .instance()
+            .get(&DataKey::DisputeBond)
+            .unwrap_or(0)
+    }
+
+    /// Get the number of a depositor's escrows that have not yet settled
+    ///
+    /// # Arguments
+    ///
+    /// * `depositor` - Address of the depositor
+    pub fn get_open_escrow_count(env: Env, depositor: Address) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::OpenEscrows(depositor))
+            .unwrap_or(0)
+    }
+
+    /// Look up the escrow tagged with an external reference
+    ///
+    /// # Arguments
+    ///
+    /// * `reference` - External reference set with `set_reference`
+    ///
+    /// # Returns
+    ///
+    /// Escrow ID, if an escrow carries the reference
+    pub fn get_escrow_by_reference(env: Env, reference: BytesN<32>) -> Option<Symbol> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Reference(reference))
+    }
+
+    /// Get the receipt of a refunded escrow
+    ///
+    /// # Arguments
+    ///
+    /// * `escrow_id` - Identifier for the escrow
+    ///
+    /// # Returns
+    ///
+    /// Refund receipt, if the escrow was refunded
+    pub fn get_refund_receipt(env: Env, escrow_id: Symbol) -> Option<RefundReceipt> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::RefundReceipt(escrow_id))
+    }
+
+    /// Read the admin audit log
+    ///
+    /// Only the most recent `ADMIN_LOG_SIZE` actions are retained. Reading
+    /// an action refreshes its TTL, so a log that is read keeps its history.
+    ///
+    /// # Arguments
+    ///
+    /// * `start` - Index of the first retained action to return, oldest first
+    /// * `limit` - Maximum number of actions to return
+    ///
+    /// # Returns
+    ///
+    /// Admin actions in the order they were performed
+    pub fn get_admin_log(env: Env, start: u32, limit: u32) -> Vec<AdminAction> {
+        Self::extend_instance_ttl(&env);
+        let count: u32 = env
+            .storage()
+            .instance()
+            .get(&DataKey::AdminLogCount)
+            .unwrap_or(0);
+        let oldest = count.saturating_sub(ADMIN_LOG_SIZE);
+        let mut actions = Vec::new(&env);
+        let first = oldest.saturating_add(start);
+        let end = first.saturating_add(limit).min(count);
+        for index in first..end {
+            let key = DataKey::AdminLog(index % ADMIN_LOG_SIZE);
+            if let Some(action) = env.storage().persistent().get(&key) {
+                Self::extend_persistent_ttl(&env, &key);
+                actions.push_back(action);
+            }
+        }
+        actions
+    }
+
+    /// List escrows currently in a given status
+    ///
+    /// An escrow leaving a status is replaced by the last one in it, so
+    /// positions are stable only while no escrow leaves `status`.
+    ///
+    /// # Arguments
+    ///
+    /// * `status` - Status to filter by
+    /// * `start` - Position of the first escrow to return
+    /// * `limit` - Maximum number of escrows to return
+    ///
+    /// # Returns
+    ///
+    /// Escrow IDs at positions `start..start + limit`
+    pub fn get_escrows_by_status(
+        env: Env,
+        status: EscrowStatus,
+        start: u32,
+        limit: u32,
+    ) -> Vec<Symbol> {
+        let end = start
+            .saturating_add(limit)
+            .min(Self::get_status_count(env.clone(), status));
+        let mut ids = Vec::new(&env);
+        for index in start..end {
+            let key = DataKey::EscrowByStatusIndex(status, index);
+            if let Some(escrow_id) = env.storage().persistent().get(&key) {
+                Self::extend_persistent_ttl(&env, &key);
+                ids.push_back(escrow_id);
+            }
+        }
+        ids
+    }
+
+    /// Get the number of escrows currently in a given status
+    pub fn get_status_count(env: Env, status: EscrowStatus) -> u32 {
+        let key = DataKey::EscrowsByStatus(status);
+        let count = env.storage().persistent().get(&key).unwrap_or(0);
+        if count > 0 {
+            Self::extend_persistent_ttl(&env, &key);
+        }
+        count
+    }
+
+    /// Get the total amount the contract currently owes to open escrows
+    ///
+    /// # Returns
+    ///
+    /// Sum of the amounts of all funded escrows not yet released or refunded,
+    /// plus the bonds of unresolved disputes
+    pub fn get_total_locked(env: Env) -> u128 {
+        env.storage()
+            .instance()
+            .get(&DataKey::TotalLocked)
+            .unwrap_or(0)
+    }
+
+    /// Check that the contract's token balance covers its escrow obligations
+    ///
+    /// Intended as a canary for tests and monitoring: a `false` result means
+    /// a release or refund has paid out more than was deposited for it.
+    ///
+    /// # Returns
+    ///
+    /// True if the contract's balance of `token` is at least what it owes
+    /// in that token. Only the escrow token is ever owed.
+    pub fn assert_solvent(env: Env, token: Address) -> bool {
+        let escrow_token: Address = env.storage().instance().get(&DataKey::Token).unwrap();
+        let owed = if token == escrow_token {
+            Self::get_total_locked(env.clone())
+        } else {
+            0
+        };
+        let balance = TokenClient::new(&env, &token).balance(&env.current_contract_address());
+        match u128::try_from(balance) {
+            Ok(balance) => balance >= owed,
+            Err(_) => false,
+        }
+    }
+
+    /// Refresh the TTL of a stored escrow or dispute record
+    ///
+    /// Keeper entry point: anyone may call it to keep a long-lived record
+    /// from being archived. Records are kept alive until their escrow's
+    /// `expires_at` plus a retention window.
+    ///
+    /// # Arguments
+    ///
+    /// * `kind` - Which kind of record `id` refers to
+    /// * `id` - Escrow ID or dispute ID
+    pub fn bump_entry(env: Env, kind: EntryKind, id: Symbol) -> Result<(), EscrowError> {
+        Self::extend_instance_ttl(&env);
+        match kind {
+            EntryKind::Escrow => {
+                Self::get_escrow_internal(&env, &id)?;
+            }
+            EntryKind::Dispute => {
+                let key = DataKey::Dispute(id);
+                let dispute: Dispute = env
+                    .storage()
+                    .persistent()
+                    .get(&key)
+                    .ok_or(EscrowError::EscrowNotFound)?;
+                let escrow = Self::get_escrow_internal(&env, &dispute.escrow_id)?;
+                Self::extend_record_ttl(&env, &key, escrow.expires_at);
+            }
+        }
+        Ok(())
+    }
+
     // ---- Internal helpers ----
 
     fn get_escrow_internal(env: &Env, escrow_id: &Symbol) -> Result<Escrow, EscrowError> {
         let key = DataKey::Escrow(escrow_id.clone());
-        env.storage()
-            .instance()
+        let escrow: Escrow = env
+            .storage()
+            .persistent()
             .get(&key)
-            .ok_or(EscrowError::EscrowNotFound)
+            .ok_or(EscrowError::EscrowNotFound)?;
+        Self::extend_record_ttl(env, &key, escrow.expires_at);
+        Ok(escrow)
+    }
+
+    fn dispute_id_for(env: &Env, escrow_id: &Symbol) -> Symbol {
+        Symbol::new(env, &format!("DISPUTE_{}", escrow_id.to_string()))
     }
 
     fn save_escrow(env: &Env, escrow: &Escrow) {
         let key = DataKey::Escrow(escrow.escrow_id.clone());
-        env.storage().instance().set(&key, escrow);
+        env.storage().persistent().set(&key, escrow);
+        Self::extend_record_ttl(env, &key, escrow.expires_at);
+    }
+
+    fn set_release_delegate(
+        env: Env,
+        escrow_id: Symbol,
+        delegate: Address,
+        allowed: bool,
+    ) -> Result<(), EscrowError> {
+        Self::extend_instance_ttl(&env);
+
+        let escrow = Self::get_escrow_internal(&env, &escrow_id)?;
+        escrow.depositor.require_auth();
+
+        let key = DataKey::ReleaseDelegate(escrow_id, delegate);
+        if allowed {
+            env.storage().persistent().set(&key, &true);
+            Self::extend_record_ttl(&env, &key, escrow.expires_at);
+        } else {
+            env.storage().persistent().remove(&key);
+        }
+
+        Ok(())
+    }
+
+    /// Append a privileged call to the admin audit log, overwriting the
+    /// oldest entry once the log is full.
+    fn log_admin_action(env: &Env, actor: &Address, action: &str, data: Bytes) {
+        let count: u32 = env
+            .storage()
+            .instance()
+            .get(&DataKey::AdminLogCount)
+            .unwrap_or(0);
+        let entry = AdminAction {
+            action: Symbol::new(env, action),
+            actor: actor.clone(),
+            timestamp: env.ledger().timestamp(),
+            data_hash: env.crypto().sha256(&data).into(),
+        };
+        let key = DataKey::AdminLog(count % ADMIN_LOG_SIZE);
+        env.storage().persistent().set(&key, &entry);
+        Self::extend_persistent_ttl(env, &key);
+        env.storage()
+            .instance()
+            .set(&DataKey::AdminLogCount, &count.saturating_add(1));
+    }
+
+    /// Return a funded escrow's amount to its depositor and store a refund
+    /// receipt for it. Every refund goes through here so each one leaves a
+    /// receipt.
+    fn refund_depositor(
+        env: &Env,
+        escrow: &Escrow,
+        dispute_id: Option<Symbol>,
+    ) -> Result<(), EscrowError> {
+        let token_addr: Address = env.storage().instance().get(&DataKey::Token).unwrap();
+        let amount_i128: i128 = escrow
+            .amount
+            .try_into()
+            .map_err(|_| EscrowError::InsufficientFunds)?;
+        TokenClient::new(env, &token_addr).transfer(
+            &env.current_contract_address(),
+            &escrow.depositor,
+            &amount_i128,
+        );
+        Self::track_locked(env, escrow.amount, false)?;
+        Self::record_outflow(env, escrow.amount);
+
+        let refunded_at = env.ledger().timestamp();
+        let refund_data = (
+            escrow.escrow_id.clone(),
+            escrow.amount,
+            refunded_at,
+            env.ledger().sequence(),
+        )
+            .to_xdr(env);
+        let receipt = RefundReceipt {
+            escrow_id: escrow.escrow_id.clone(),
+            dispute_id,
+            depositor: escrow.depositor.clone(),
+            amount: escrow.amount,
+            token: token_addr,
+            refunded_at,
+            refund_id: env.crypto().sha256(&refund_data).into(),
+        };
+        let receipt_key = DataKey::RefundReceipt(escrow.escrow_id.clone());
+        env.storage().persistent().set(&receipt_key, &receipt);
+        Self::extend_record_ttl(env, &receipt_key, escrow.expires_at);
+        Ok(())
+    }
+
+    /// Account released funds are paid to.
+    fn payout_recipient(escrow: &Escrow) -> Address {
+        escrow
+            .payout_address
+            .clone()
+            .unwrap_or(escrow.beneficiary.clone())
+    }
+
+    fn require_not_paused(env: &Env) -> Result<(), EscrowError> {
+        if Self::is_paused(env.clone()) {
+            return Err(EscrowError::ContractPaused);
+        }
+        Ok(())
+    }
+
+    /// Add a payout to the current outflow window and trip the circuit
+    /// breaker if the window's limit is exceeded. The payout that trips it
+    /// still completes; later payouts are blocked until `unpause`.
+    fn record_outflow(env: &Env, amount: u128) {
+        let breaker: CircuitBreaker = match env.storage().instance().get(&DataKey::CircuitBreaker) {
+            Some(breaker) => breaker,
+            None => return,
+        };
+        if breaker.max_outflow_per_window == 0 {
+            return;
+        }
+
+        let now = env.ledger().timestamp();
+        let mut window: OutflowWindow =
+            env.storage()
+                .instance()
+                .get(&DataKey::Outflow)
+                .unwrap_or(OutflowWindow {
+                    window_start: now,
+                    outflow: 0,
+                });
+        if now >= window.window_start.saturating_add(breaker.outflow_window) {
+            window = OutflowWindow {
+                window_start: now,
+                outflow: 0,
+            };
+        }
+        window.outflow = window.outflow.saturating_add(amount);
+        env.storage().instance().set(&DataKey::Outflow, &window);
+
+        if window.outflow > breaker.max_outflow_per_window {
+            env.storage().instance().set(&DataKey::Paused, &true);
+            env.events().publish(
+                (Symbol::new(env, "circuit_breaker_tripped"),),
+                (window.outflow, breaker.max_outflow_per_window),
+            );
+        }
+    }
+
+    /// Move an escrow to a new status, keeping the status index in step.
+    fn set_status(env: &Env, escrow: &mut Escrow, status: EscrowStatus) {
+        Self::index_status(env, &escrow.escrow_id, escrow.status, false);
+        Self::index_status(env, &escrow.escrow_id, status, true);
+        escrow.status = status;
+    }
+
+    /// Add (`present`) or remove an escrow ID from a status index. Removal
+    /// moves the last ID in the index into the freed position.
+    fn index_status(env: &Env, escrow_id: &Symbol, status: EscrowStatus, present: bool) {
+        let count_key = DataKey::EscrowsByStatus(status);
+        let count = Self::get_status_count(env.clone(), status);
+        let position_key = DataKey::StatusIndexPosition(escrow_id.clone());
+
+        let count = if present {
+            Self::set_status_entry(env, status, count, escrow_id);
+            count + 1
+        } else {
+            let position: u32 = match env.storage().persistent().get(&position_key) {
+                Some(position) => position,
+                None => return,
+            };
+            let last = count - 1;
+            let last_key = DataKey::EscrowByStatusIndex(status, last);
+            if position != last {
+                let moved: Symbol = env.storage().persistent().get(&last_key).unwrap();
+                Self::set_status_entry(env, status, position, &moved);
+            }
+            env.storage().persistent().remove(&last_key);
+            env.storage().persistent().remove(&position_key);
+            last
+        };
+
+        env.storage().persistent().set(&count_key, &count);
+        Self::extend_persistent_ttl(env, &count_key);
+    }
+
+    fn set_status_entry(env: &Env, status: EscrowStatus, position: u32, escrow_id: &Symbol) {
+        let entry_key = DataKey::EscrowByStatusIndex(status, position);
+        env.storage().persistent().set(&entry_key, escrow_id);
+        Self::extend_persistent_ttl(env, &entry_key);
+        let position_key = DataKey::StatusIndexPosition(escrow_id.clone());
+        env.storage().persistent().set(&position_key, &position);
+        Self::extend_persistent_ttl(env, &position_key);
+    }
+
+    /// Add (`locked`) or remove an escrow amount or dispute bond from the
+    /// running total of funds the contract owes. Fails rather than wrapping if the total
+    /// would go out of range, since that means the accounting is wrong.
+    fn track_locked(env: &Env, amount: u128, locked: bool) -> Result<(), EscrowError> {
+        let total = Self::get_total_locked(env.clone());
+        let total = if locked {
+            total.checked_add(amount)
+        } else {
+            total.checked_sub(amount)
+        }
+        .ok_or(EscrowError::InsufficientFunds)?;
+        env.storage().instance().set(&DataKey::TotalLocked, &total);
+        Ok(())
+    }
+
+    /// Count an escrow as opened (`open`) or settled for its depositor.
+    fn track_open_escrow(env: &Env, depositor: &Address, open: bool) {
+        let count = Self::get_open_escrow_count(env.clone(), depositor.clone());
+        let count = if open {
+            count.saturating_add(1)
+        } else {
+            count.saturating_sub(1)
+        };
+        let key = DataKey::OpenEscrows(depositor.clone());
+        env.storage().persistent().set(&key, &count);
+        Self::extend_persistent_ttl(env, &key);
+    }
+
+    /// Keep a persistent record live until `expires_at` plus the retention window.
+    fn extend_record_ttl(env: &Env, key: &DataKey, expires_at: u64) {
+        let seconds_left = expires_at.saturating_sub(env.ledger().timestamp());
+        let extend_to = u32::try_from(seconds_left / LEDGER_CLOSE_SECONDS)
+            .unwrap_or(u32::MAX)
+            .saturating_add(TTL_RETENTION)
+            .min(env.storage().max_ttl());
+        env.storage().persistent().extend_ttl(
+            key,
+            extend_to.saturating_sub(DAY_IN_LEDGERS),
+            extend_to,
+        );
+    }
+
+    fn extend_persistent_ttl(env: &Env, key: &DataKey) {
+        env.storage()
+            .persistent()
+            .extend_ttl(key, TTL_PERSISTENT - DAY_IN_LEDGERS, TTL_PERSISTENT);
+    }
+
+    fn extend_instance_ttl(env: &Env) {
+        env.storage()
+            .instance()
+            .extend_ttl(TTL_INSTANCE - DAY_IN_LEDGERS, TTL_INSTANCE);
     }
 }
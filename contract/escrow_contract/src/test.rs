@@ -0,0 +1,679 @@
+use crate::{
+    DataKey, EscrowContract, EscrowContractClient, EscrowError, EscrowStatus, RefundReceipt,
+};
+use soroban_sdk::{
+    contract, contractimpl,
+    testutils::{storage::Persistent as _, Address as _, Ledger},
+    token, vec,
+    xdr::ToXdr,
+    Address, BytesN, Env, String, Symbol,
+};
+
+struct Setup {
+    env: Env,
+    client: EscrowContractClient<'static>,
+    token: token::Client<'static>,
+    admin: Address,
+    depositor: Address,
+    beneficiary: Address,
+}
+
+fn setup() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(1_700_000_000);
+
+    let token_id = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    let admin = Address::generate(&env);
+    let depositor = Address::generate(&env);
+    let beneficiary = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &token_id).mint(&depositor, &1_000_000);
+
+    let contract_id = env.register(EscrowContract, ());
+    let client = EscrowContractClient::new(&env, &contract_id);
+    client.initialize(&token_id, &admin);
+
+    Setup {
+        client,
+        token: token::Client::new(&env, &token_id),
+        admin,
+        depositor,
+        beneficiary,
+        env,
+    }
+}
+
+/// Create an escrow of `amount` needing only the depositor's confirmation.
+fn create(s: &Setup, amount: u128, key: u8) -> Symbol {
+    s.client.create_escrow(
+        &s.depositor,
+        &s.beneficiary,
+        &amount,
+        &(s.env.ledger().timestamp() + 1_000),
+        &String::from_str(&s.env, "terms"),
+        &1,
+        &BytesN::from_array(&s.env, &[key; 32]),
+    )
+}
+
+fn create_funded(s: &Setup, amount: u128, key: u8) -> Symbol {
+    let escrow_id = create(s, amount, key);
+    s.client.fund_escrow(&escrow_id);
+    escrow_id
+}
+
+// ---------------------------------------------------------------------------
+// Solvency Tests
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_solvent_after_funding_and_release() {
+    let s = setup();
+    let first = create_funded(&s, 300, 1);
+    create_funded(&s, 200, 2);
+    assert_eq!(s.client.get_total_locked(), 500);
+    assert!(s.client.assert_solvent(&s.token.address));
+
+    s.client.release_funds(&first, &s.depositor);
+    assert_eq!(s.client.get_total_locked(), 200);
+    assert_eq!(s.token.balance(&s.client.address), 200);
+    assert!(s.client.assert_solvent(&s.token.address));
+}
+
+#[test]
+fn test_shortfall_detected() {
+    let s = setup();
+    create_funded(&s, 300, 1);
+
+    // Drain part of the balance behind the contract's back.
+    s.env.as_contract(&s.client.address, || {
+        s.token
+            .transfer(&s.client.address, Address::generate(&s.env), &1);
+    });
+    assert!(!s.client.assert_solvent(&s.token.address));
+}
+
+#[test]
+fn test_solvency_checked_per_token() {
+    let s = setup();
+    create_funded(&s, 300, 1);
+    let other = s
+        .env
+        .register_stellar_asset_contract_v2(Address::generate(&s.env))
+        .address();
+
+    // Nothing is owed in a token the escrows are not held in.
+    assert!(s.client.assert_solvent(&other));
+    assert!(s.client.assert_solvent(&s.token.address));
+}
+
+// ---------------------------------------------------------------------------
+// Idempotency Tests
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_reused_idempotency_key_rejected() {
+    let s = setup();
+    let first = create(&s, 100, 1);
+
+    let retry = s.client.try_create_escrow(
+        &s.depositor,
+        &s.beneficiary,
+        &100,
+        &(s.env.ledger().timestamp() + 1_000),
+        &String::from_str(&s.env, "terms"),
+        &1,
+        &BytesN::from_array(&s.env, &[1; 32]),
+    );
+    assert_eq!(retry, Err(Ok(EscrowError::EscrowAlreadyExists)));
+
+    let second = create(&s, 100, 2);
+    assert_ne!(first, second);
+    assert_eq!(s.client.get_escrow(&first).status, EscrowStatus::Pending);
+    assert_eq!(s.client.get_escrow(&second).status, EscrowStatus::Pending);
+}
+
+// ---------------------------------------------------------------------------
+// Open Escrow Cap Tests
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_expired_escrow_frees_open_slot() {
+    let s = setup();
+    s.client.set_max_open_escrows(&2);
+    let first = create_funded(&s, 100, 1);
+    create(&s, 100, 2);
+    assert_eq!(s.client.get_open_escrow_count(&s.depositor), 2);
+
+    let result = s.client.try_create_escrow(
+        &s.depositor,
+        &s.beneficiary,
+        &100,
+        &(s.env.ledger().timestamp() + 1_000),
+        &String::from_str(&s.env, "terms"),
+        &1,
+        &BytesN::from_array(&s.env, &[3; 32]),
+    );
+    assert_eq!(result, Err(Ok(EscrowError::EscrowLimitReached)));
+
+    // Not expired yet.
+    let result = s.client.try_expire_escrow(&first);
+    assert_eq!(result, Err(Ok(EscrowError::InvalidTerms)));
+
+    s.env.ledger().with_mut(|li| li.timestamp += 1_000);
+    let result = s.client.try_release_funds(&first, &s.depositor);
+    assert_eq!(result, Err(Ok(EscrowError::EscrowExpired)));
+
+    let balance_before = s.token.balance(&s.depositor);
+    assert!(s.client.expire_escrow(&first));
+    assert_eq!(s.client.get_escrow(&first).status, EscrowStatus::Expired);
+    assert_eq!(s.token.balance(&s.depositor), balance_before + 100);
+    assert_eq!(s.client.get_total_locked(), 0);
+    assert_eq!(s.client.get_open_escrow_count(&s.depositor), 1);
+
+    create(&s, 100, 3);
+    assert_eq!(s.client.get_open_escrow_count(&s.depositor), 2);
+}
+
+// ---------------------------------------------------------------------------
+// Dispute Bond Tests
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_losing_initiator_forfeits_bond() {
+    let s = setup();
+    s.client.set_dispute_bond(&50);
+    token::StellarAssetClient::new(&s.env, &s.token.address).mint(&s.beneficiary, &50);
+    let escrow_id = create_funded(&s, 500, 1);
+    let depositor_balance = s.token.balance(&s.depositor);
+
+    let dispute_id = s.client.create_dispute(
+        &escrow_id,
+        &s.beneficiary,
+        &String::from_str(&s.env, "not paid on time"),
+    );
+    assert_eq!(s.token.balance(&s.beneficiary), 0);
+
+    s.client
+        .resolve_dispute(&dispute_id, &String::from_str(&s.env, "refund"));
+    assert_eq!(s.token.balance(&s.beneficiary), 0);
+    assert_eq!(s.token.balance(&s.depositor), depositor_balance + 550);
+}
+
+#[test]
+fn test_bond_counted_as_locked_until_resolved() {
+    let s = setup();
+    s.client.set_dispute_bond(&50);
+    let escrow_id = create_funded(&s, 500, 1);
+
+    let dispute_id = s.client.create_dispute(
+        &escrow_id,
+        &s.depositor,
+        &String::from_str(&s.env, "event cancelled"),
+    );
+    assert_eq!(s.client.get_total_locked(), 550);
+    assert!(s.client.assert_solvent(&s.token.address));
+
+    s.client
+        .resolve_dispute(&dispute_id, &String::from_str(&s.env, "release"));
+    assert_eq!(s.client.get_total_locked(), 0);
+    assert!(s.client.assert_solvent(&s.token.address));
+}
+
+#[test]
+fn test_unfunded_escrow_cannot_be_disputed() {
+    let s = setup();
+    let escrow_id = create(&s, 500, 1);
+
+    let result = s.client.try_create_dispute(
+        &escrow_id,
+        &s.beneficiary,
+        &String::from_str(&s.env, "never funded"),
+    );
+    assert_eq!(result, Err(Ok(EscrowError::InsufficientFunds)));
+    assert_eq!(s.token.balance(&s.client.address), 0);
+}
+
+#[test]
+fn test_winning_initiator_gets_bond_back() {
+    let s = setup();
+    s.client.set_dispute_bond(&50);
+    let escrow_id = create_funded(&s, 500, 1);
+    let depositor_balance = s.token.balance(&s.depositor);
+
+    let dispute_id = s.client.create_dispute(
+        &escrow_id,
+        &s.depositor,
+        &String::from_str(&s.env, "event cancelled"),
+    );
+    assert_eq!(s.token.balance(&s.depositor), depositor_balance - 50);
+
+    s.client
+        .resolve_dispute(&dispute_id, &String::from_str(&s.env, "refund"));
+    assert_eq!(s.token.balance(&s.depositor), depositor_balance + 500);
+    assert_eq!(s.token.balance(&s.beneficiary), 0);
+    assert_eq!(s.token.balance(&s.client.address), 0);
+}
+
+// ---------------------------------------------------------------------------
+// Status Index Tests
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_status_index_follows_transitions() {
+    let s = setup();
+    let first = create(&s, 100, 1);
+    let second = create(&s, 100, 2);
+    let third = create(&s, 100, 3);
+    assert_eq!(s.client.get_status_count(&EscrowStatus::Pending), 3);
+
+    s.client.fund_escrow(&first);
+    s.client.fund_escrow(&third);
+    s.client.release_funds(&third, &s.depositor);
+
+    let pending = s
+        .client
+        .get_escrows_by_status(&EscrowStatus::Pending, &0, &10);
+    assert_eq!(pending, vec![&s.env, second.clone()]);
+    let funded = s
+        .client
+        .get_escrows_by_status(&EscrowStatus::Funded, &0, &10);
+    assert_eq!(funded, vec![&s.env, first.clone()]);
+    let completed = s
+        .client
+        .get_escrows_by_status(&EscrowStatus::Completed, &0, &10);
+    assert_eq!(completed, vec![&s.env, third]);
+
+    // Pages of the Pending index after the swap-remove of `first`.
+    let fourth = create(&s, 100, 4);
+    assert_eq!(
+        s.client
+            .get_escrows_by_status(&EscrowStatus::Pending, &0, &1),
+        vec![&s.env, second]
+    );
+    assert_eq!(
+        s.client
+            .get_escrows_by_status(&EscrowStatus::Pending, &1, &5),
+        vec![&s.env, fourth]
+    );
+    assert_eq!(
+        s.client
+            .get_escrows_by_status(&EscrowStatus::Pending, &2, &5)
+            .len(),
+        0
+    );
+}
+
+// ---------------------------------------------------------------------------
+// Reference Tests
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_escrow_found_by_reference() {
+    let s = setup();
+    let escrow_id = create(&s, 100, 1);
+    let reference = BytesN::from_array(&s.env, &[9; 32]);
+    let memo = String::from_str(&s.env, "order 1042");
+
+    assert_eq!(s.client.get_escrow_by_reference(&reference), None);
+    s.client.set_reference(&escrow_id, &reference, &memo);
+
+    assert_eq!(
+        s.client.get_escrow_by_reference(&reference),
+        Some(escrow_id.clone())
+    );
+    let escrow = s.client.get_escrow(&escrow_id);
+    assert_eq!(escrow.reference, Some(reference.clone()));
+    assert_eq!(escrow.memo, memo);
+
+    // A reference can only point at one escrow.
+    let other = create(&s, 100, 2);
+    let result = s.client.try_set_reference(&other, &reference, &memo);
+    assert_eq!(result, Err(Ok(EscrowError::EscrowAlreadyExists)));
+}
+
+// ---------------------------------------------------------------------------
+// Release Delegate Tests
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_delegate_releases_for_depositor() {
+    let s = setup();
+    let escrow_id = create_funded(&s, 500, 1);
+    let staff = Address::generate(&s.env);
+
+    let result = s.client.try_release_funds(&escrow_id, &staff);
+    assert_eq!(result, Err(Ok(EscrowError::Unauthorized)));
+
+    s.client.add_release_delegate(&escrow_id, &staff);
+    assert!(s.client.is_release_delegate(&escrow_id, &staff));
+    let result = s.client.try_create_dispute(
+        &escrow_id,
+        &staff,
+        &String::from_str(&s.env, "delegates cannot dispute"),
+    );
+    assert_eq!(result, Err(Ok(EscrowError::Unauthorized)));
+
+    assert!(s.client.release_funds(&escrow_id, &staff));
+    assert_eq!(s.token.balance(&s.beneficiary), 500);
+}
+
+#[test]
+fn test_revoked_delegate_rejected() {
+    let s = setup();
+    let escrow_id = create_funded(&s, 500, 1);
+    let staff = Address::generate(&s.env);
+
+    s.client.add_release_delegate(&escrow_id, &staff);
+    s.client.remove_release_delegate(&escrow_id, &staff);
+    assert!(!s.client.is_release_delegate(&escrow_id, &staff));
+
+    let result = s.client.try_release_funds(&escrow_id, &staff);
+    assert_eq!(result, Err(Ok(EscrowError::Unauthorized)));
+    assert_eq!(s.client.get_escrow(&escrow_id).status, EscrowStatus::Funded);
+}
+
+// ---------------------------------------------------------------------------
+// Admin Log Tests
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_admin_actions_logged_in_order() {
+    let s = setup();
+    s.client.set_max_open_escrows(&5);
+    s.client.set_dispute_bond(&50);
+    s.client.unpause();
+
+    let log = s.client.get_admin_log(&0, &10);
+    assert_eq!(log.len(), 3);
+    let actions: [&str; 3] = ["set_max_open", "set_dispute_bond", "unpause"];
+    for (entry, action) in log.iter().zip(actions) {
+        assert_eq!(entry.action, Symbol::new(&s.env, action));
+        assert_eq!(entry.actor, s.admin);
+    }
+    assert_eq!(
+        s.client.get_admin_log(&1, &1).get(0).unwrap().action,
+        Symbol::new(&s.env, "set_dispute_bond")
+    );
+}
+
+#[test]
+fn test_reading_admin_log_refreshes_entry_ttl() {
+    let s = setup();
+    s.client.set_max_open_escrows(&5);
+
+    let slot_ttl = || {
+        s.env.as_contract(&s.client.address, || {
+            s.env.storage().persistent().get_ttl(&DataKey::AdminLog(0))
+        })
+    };
+
+    // Sixty days on, the entry written with a 90 day TTL has 30 days left.
+    s.env
+        .ledger()
+        .with_mut(|li| li.sequence_number += 17_280 * 60);
+    assert_eq!(slot_ttl(), 17_280 * 30);
+
+    assert_eq!(s.client.get_admin_log(&0, &10).len(), 1);
+    assert_eq!(slot_ttl(), 17_280 * 90);
+}
+
+// ---------------------------------------------------------------------------
+// Release Condition Tests
+// ---------------------------------------------------------------------------
+
+#[contract]
+struct MockAttestation;
+
+#[contractimpl]
+impl MockAttestation {
+    pub fn set_attested(env: Env, attested: bool) {
+        env.storage()
+            .instance()
+            .set(&Symbol::new(&env, "attested"), &attested);
+    }
+
+    pub fn event_occurred(env: Env, _escrow_id: Symbol) -> bool {
+        env.storage()
+            .instance()
+            .get(&Symbol::new(&env, "attested"))
+            .unwrap_or(false)
+    }
+}
+
+#[test]
+fn test_release_waits_for_attestation() {
+    let s = setup();
+    let attestation = s.env.register(MockAttestation, ());
+    let attestation_client = MockAttestationClient::new(&s.env, &attestation);
+
+    let escrow_id = create(&s, 500, 1);
+    s.client.set_release_condition(
+        &escrow_id,
+        &attestation,
+        &Symbol::new(&s.env, "event_occurred"),
+    );
+    s.client.fund_escrow(&escrow_id);
+
+    let result = s.client.try_release_funds(&escrow_id, &s.depositor);
+    assert_eq!(result, Err(Ok(EscrowError::ConditionNotMet)));
+    assert_eq!(s.client.get_escrow(&escrow_id).status, EscrowStatus::Funded);
+    assert_eq!(s.token.balance(&s.beneficiary), 0);
+
+    attestation_client.set_attested(&true);
+    assert!(s.client.release_funds(&escrow_id, &s.depositor));
+    assert_eq!(
+        s.client.get_escrow(&escrow_id).status,
+        EscrowStatus::Completed
+    );
+    assert_eq!(s.token.balance(&s.beneficiary), 500);
+}
+
+#[test]
+fn test_release_without_condition_needs_no_attestation() {
+    let s = setup();
+    let escrow_id = create_funded(&s, 500, 1);
+
+    assert!(s.client.release_funds(&escrow_id, &s.depositor));
+    assert_eq!(s.token.balance(&s.beneficiary), 500);
+}
+
+// ---------------------------------------------------------------------------
+// Circuit Breaker Tests
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_circuit_breaker_pauses_payouts_until_unpaused() {
+    let s = setup();
+    s.client.set_circuit_breaker(&500, &3_600);
+    let expiring = create_funded(&s, 100, 1);
+    s.env.ledger().with_mut(|li| li.timestamp += 500);
+    let first = create_funded(&s, 300, 2);
+    let second = create_funded(&s, 300, 3);
+    let pending = create_funded(&s, 100, 4);
+    let disputed = create_funded(&s, 100, 5);
+    let dispute_id = s.client.create_dispute(
+        &disputed,
+        &s.depositor,
+        &String::from_str(&s.env, "event cancelled"),
+    );
+
+    // The payout that crosses the limit still goes through.
+    s.client.release_funds(&first, &s.depositor);
+    assert!(!s.client.is_paused());
+    s.client.release_funds(&second, &s.depositor);
+    assert!(s.client.is_paused());
+    assert_eq!(s.token.balance(&s.beneficiary), 600);
+
+    s.env.ledger().with_mut(|li| li.timestamp += 600);
+    let refund = String::from_str(&s.env, "refund");
+    assert_eq!(
+        s.client.try_release_funds(&pending, &s.depositor),
+        Err(Ok(EscrowError::ContractPaused))
+    );
+    assert_eq!(
+        s.client.try_resolve_dispute(&dispute_id, &refund),
+        Err(Ok(EscrowError::ContractPaused))
+    );
+    assert_eq!(
+        s.client.try_expire_escrow(&expiring),
+        Err(Ok(EscrowError::ContractPaused))
+    );
+    assert_eq!(s.token.balance(&s.client.address), 300);
+
+    s.client.unpause();
+    assert!(s.client.release_funds(&pending, &s.depositor));
+    assert!(s.client.resolve_dispute(&dispute_id, &refund));
+    assert!(s.client.expire_escrow(&expiring));
+    assert_eq!(s.token.balance(&s.client.address), 0);
+    assert!(!s.client.is_paused());
+}
+
+// ---------------------------------------------------------------------------
+// Payout Address Tests
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_release_pays_payout_address_when_set() {
+    let s = setup();
+    let treasury = Address::generate(&s.env);
+    let escrow_id = create_funded(&s, 400, 1);
+    s.client
+        .set_payout_address(&escrow_id, &Some(treasury.clone()));
+
+    // The beneficiary still confirms; only the receiving account changes.
+    assert!(s.client.release_funds(&escrow_id, &s.beneficiary));
+    assert_eq!(s.token.balance(&treasury), 400);
+    assert_eq!(s.token.balance(&s.beneficiary), 0);
+}
+
+#[test]
+fn test_release_pays_beneficiary_without_payout_address() {
+    let s = setup();
+    let treasury = Address::generate(&s.env);
+    let escrow_id = create_funded(&s, 400, 1);
+    s.client
+        .set_payout_address(&escrow_id, &Some(treasury.clone()));
+    s.client.set_payout_address(&escrow_id, &None);
+
+    assert!(s.client.release_funds(&escrow_id, &s.depositor));
+    assert_eq!(s.token.balance(&s.beneficiary), 400);
+    assert_eq!(s.token.balance(&treasury), 0);
+}
+
+#[test]
+fn test_dispute_release_pays_payout_address() {
+    let s = setup();
+    let treasury = Address::generate(&s.env);
+    let escrow_id = create_funded(&s, 400, 1);
+    s.client
+        .set_payout_address(&escrow_id, &Some(treasury.clone()));
+    let dispute_id = s.client.create_dispute(
+        &escrow_id,
+        &s.beneficiary,
+        &String::from_str(&s.env, "not paid on time"),
+    );
+
+    s.client
+        .resolve_dispute(&dispute_id, &String::from_str(&s.env, "release"));
+    assert_eq!(s.token.balance(&treasury), 400);
+    assert_eq!(s.token.balance(&s.beneficiary), 0);
+}
+
+// ---------------------------------------------------------------------------
+// Refund Receipt Tests
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_refund_stores_matching_receipt() {
+    let s = setup();
+    let escrow_id = create_funded(&s, 250, 1);
+    let dispute_id = s.client.create_dispute(
+        &escrow_id,
+        &s.depositor,
+        &String::from_str(&s.env, "event cancelled"),
+    );
+    assert_eq!(s.client.get_refund_receipt(&escrow_id), None);
+
+    s.env.ledger().with_mut(|li| li.timestamp += 60);
+    s.client
+        .resolve_dispute(&dispute_id, &String::from_str(&s.env, "refund"));
+
+    assert_eq!(
+        s.client.get_refund_receipt(&escrow_id),
+        Some(expected_receipt(&s, &escrow_id, Some(dispute_id), 250))
+    );
+}
+
+#[test]
+fn test_expiry_refund_stores_receipt() {
+    let s = setup();
+    let escrow_id = create_funded(&s, 250, 1);
+
+    s.env.ledger().with_mut(|li| li.timestamp += 1_000);
+    s.client.expire_escrow(&escrow_id);
+
+    assert_eq!(s.token.balance(&s.client.address), 0);
+    assert_eq!(
+        s.client.get_refund_receipt(&escrow_id),
+        Some(expected_receipt(&s, &escrow_id, None, 250))
+    );
+}
+
+#[test]
+fn test_refund_ids_differ_between_escrows() {
+    let s = setup();
+    let first = create_funded(&s, 250, 1);
+    let second = create_funded(&s, 250, 2);
+
+    s.env.ledger().with_mut(|li| li.timestamp += 1_000);
+    s.client.expire_escrow(&first);
+    s.client.expire_escrow(&second);
+
+    let first = s.client.get_refund_receipt(&first).unwrap();
+    let second = s.client.get_refund_receipt(&second).unwrap();
+    assert_ne!(first.refund_id, second.refund_id);
+}
+
+/// Receipt a refund of `amount` made at the current ledger should leave.
+fn expected_receipt(
+    s: &Setup,
+    escrow_id: &Symbol,
+    dispute_id: Option<Symbol>,
+    amount: u128,
+) -> RefundReceipt {
+    let refunded_at = s.env.ledger().timestamp();
+    let refund_data = (
+        escrow_id.clone(),
+        amount,
+        refunded_at,
+        s.env.ledger().sequence(),
+    )
+        .to_xdr(&s.env);
+    RefundReceipt {
+        escrow_id: escrow_id.clone(),
+        dispute_id,
+        depositor: s.depositor.clone(),
+        amount,
+        token: s.token.address.clone(),
+        refunded_at,
+        refund_id: s.env.crypto().sha256(&refund_data).into(),
+    }
+}
+
+#[test]
+fn test_release_stores_no_receipt() {
+    let s = setup();
+    let escrow_id = create_funded(&s, 250, 1);
+    let dispute_id = s.client.create_dispute(
+        &escrow_id,
+        &s.beneficiary,
+        &String::from_str(&s.env, "not paid on time"),
+    );
+
+    s.client
+        .resolve_dispute(&dispute_id, &String::from_str(&s.env, "release"));
+    assert_eq!(s.client.get_refund_receipt(&escrow_id), None);
+}
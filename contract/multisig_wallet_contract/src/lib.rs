@@ -14,6 +14,7 @@
 //! - Transaction history tracking
 //! - Time-lock for critical operations
 //! - Integration with escrow for enhanced security
+//! - Pre-authorized transfers released by an oracle condition
 //!
 //! ## Modules
 //!
@@ -23,8 +24,8 @@
 //! - `governance`: Owner management and voting
 
 use soroban_sdk::{
-    contract, contracterror, contractimpl, contracttype, Address, Env, FromVal, String, Symbol,
-    Val, Vec,
+    contract, contracterror, contractimpl, contracttype, token, vec, xdr::ToXdr, Address, Bytes,
+    BytesN, Env, FromVal, IntoVal, String, Symbol, TryFromVal, Val, Vec,
 };
 
 /// Errors that can occur during multisig operations
@@ -51,6 +52,14 @@ pub enum MultisigError {
     WalletLocked = 9,
     /// Duplicate signature
     DuplicateSignature = 10,
+    /// Destination is not on the recipient allowlist
+    RecipientNotAllowed = 11,
+    /// Signer label or metadata URI exceeds its maximum length
+    MetadataTooLong = 12,
+    /// Intent does not exist
+    IntentNotFound = 13,
+    /// Intent condition is not met
+    ConditionNotMet = 14,
     /// Functionality not implemented yet
     NotImplemented = 255,
 }
@@ -107,6 +116,89 @@ pub struct MultisigConfig {
     pub timelock: u64,
     /// Maximum transaction amount
     pub max_transaction_amount: i128,
+    /// Restrict transaction destinations, or the recipient of token
+    /// transfers, to the recipient allowlist
+    pub allowlist_enabled: bool,
+}
+
+/// Aggregate wallet state for dashboards
+#[contracttype]
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct WalletSummary {
+    /// Number of wallet owners
+    pub owner_count: u32,
+    /// Number of owners not deactivated
+    pub active_signer_count: u32,
+    /// Number of signatures required
+    pub threshold: u32,
+    /// Time-lock period for transactions
+    pub timelock: u64,
+    /// Maximum transaction amount
+    pub max_transaction_amount: i128,
+    /// Whether destinations are restricted to the recipient allowlist
+    pub allowlist_enabled: bool,
+    /// Transactions submitted but not yet executed or marked expired
+    pub pending_transactions: u32,
+}
+
+/// Human-readable details attached to a signer for wallet management UIs
+#[contracttype]
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct SignerMetadata {
+    /// Display label for the signer
+    pub label: String,
+    /// URI pointing at off-chain contact or profile metadata
+    pub metadata_uri: String,
+}
+
+/// Record of a governance change, kept in the admin audit log
+#[contracttype]
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct AdminAction {
+    /// Name of the governance operation
+    pub action: Symbol,
+    /// Owner that submitted the governing transaction
+    pub actor: Address,
+    /// Ledger timestamp of the change
+    pub timestamp: u64,
+    /// SHA-256 of the XDR-encoded operation arguments
+    pub data_hash: BytesN<32>,
+}
+
+/// Oracle condition an intent waits on: `oracle.price(pair) < below`
+#[contracttype]
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct IntentCondition {
+    /// Price oracle to read
+    pub oracle: Address,
+    /// Asset pair passed to the oracle
+    pub pair: Symbol,
+    /// Price the oracle must fall below
+    pub below: i128,
+}
+
+/// Transfer pre-authorized by the owners, released once its condition holds
+#[contracttype]
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Intent {
+    /// Unique intent identifier
+    pub intent_id: u32,
+    /// Condition checked at execution
+    pub condition: IntentCondition,
+    /// Token to transfer from the wallet
+    pub token: Address,
+    /// Recipient of the transfer
+    pub recipient: Address,
+    /// Amount to transfer
+    pub amount: i128,
+    /// Timestamp after which the intent can no longer execute
+    pub expires_at: u64,
+    /// Owners whose approval of the governing transaction authorized it
+    pub approvers: Vec<Address>,
+    /// Whether the transfer has been made
+    pub executed: bool,
+    /// Whether the owners have cancelled the intent
+    pub cancelled: bool,
 }
 
 #[contracttype]
@@ -116,8 +208,30 @@ enum DataKey {
     Transaction(Symbol),
     Initialized,
     TxCount,
+    SignerMetadata(Address),
+    RecipientAllowlist(Address),
+    InactiveSigner(Address),
+    SignerLastActive(Address),
+    PendingCount,
+    AmountThresholds,
+    Intent(u32),
+    IntentCount,
+    AdminLogCount,
+    AdminLog(u32),
 }
 
+const DAY_IN_LEDGERS: u32 = 17280;
+const LEDGER_CLOSE_SECONDS: u64 = 5;
+const TTL_INSTANCE: u32 = DAY_IN_LEDGERS * 30; // 30 days
+const TTL_RETENTION: u32 = DAY_IN_LEDGERS * 30; // 30 days past expiry
+const TTL_PERSISTENT: u32 = DAY_IN_LEDGERS * 90; // 90 days
+
+/// Number of most recent governance changes kept in the audit log.
+const ADMIN_LOG_SIZE: u32 = 100;
+
+const MAX_LABEL_LEN: u32 = 64;
+const MAX_METADATA_URI_LEN: u32 = 256;
+
 /// Main contract implementation
 #[contract]
 pub struct MultisigWalletContract;
@@ -156,10 +270,12 @@ impl MultisigWalletContract {
             threshold,
             timelock,
             max_transaction_amount: max_amount,
+            allowlist_enabled: false,
         };
 
         env.storage().instance().set(&DataKey::Config, &config);
         env.storage().instance().set(&DataKey::Initialized, &true);
+        Self::extend_instance_ttl(&env);
 
         Ok(true)
     }
@@ -190,9 +306,12 @@ impl MultisigWalletContract {
         expires_at: u64,
     ) -> Result<Symbol, MultisigError> {
         creator.require_auth();
+        Self::extend_instance_ttl(&env);
         let config = Self::get_config(env.clone());
 
-        if !config.owners.contains(&creator) {
+        if !config.owners.contains(&creator)
+            || !Self::is_signer_active(env.clone(), creator.clone())
+        {
             return Err(MultisigError::Unauthorized);
         }
 
@@ -200,6 +319,16 @@ impl MultisigWalletContract {
             return Err(MultisigError::InvalidTransaction);
         }
 
+        // Calls on the wallet itself stay allowed so governance can never be
+        // locked out by its own allowlist.
+        if config.allowlist_enabled && destination != env.current_contract_address() {
+            let recipient = Self::transfer_recipient(&env, &function, &data)?
+                .unwrap_or_else(|| destination.clone());
+            if !Self::is_allowed_recipient(env.clone(), recipient) {
+                return Err(MultisigError::RecipientNotAllowed);
+            }
+        }
+
         if expires_at <= env.ledger().timestamp() {
             return Err(MultisigError::InvalidTransaction);
         }
@@ -248,17 +377,17 @@ impl MultisigWalletContract {
             status: TransactionStatus::Pending,
             created_at: env.ledger().timestamp(),
             expires_at,
-            required_confirmations: config.threshold,
+            required_confirmations: Self::required_for_amount(&env, &config, amount),
             confirmations: Vec::new(&env),
-            creator,
+            creator: creator.clone(),
         };
 
-        env.storage()
-            .persistent()
-            .set(&DataKey::Transaction(tx_id_symbol.clone()), &transaction);
+        Self::save_transaction(&env, &transaction);
         env.storage()
             .instance()
             .set(&DataKey::TxCount, &(tx_count + 1));
+        Self::track_pending(&env, true);
+        Self::record_activity(&env, &creator);
 
         Ok(tx_id_symbol)
     }
@@ -278,27 +407,69 @@ impl MultisigWalletContract {
         approver: Address,
     ) -> Result<bool, MultisigError> {
         approver.require_auth();
+        Self::extend_instance_ttl(&env);
+        Self::approve_internal(&env, transaction_id, approver)
+    }
+
+    /// Approve several transactions in one call
+    ///
+    /// Each transaction is approved as by `approve_transaction`; ones that
+    /// cannot be approved (already signed, no longer pending, expired,
+    /// created by the approver) are skipped.
+    ///
+    /// # Arguments
+    ///
+    /// * `transaction_ids` - Identifiers of the transactions to approve
+    /// * `approver` - Address of the approving owner
+    ///
+    /// # Returns
+    ///
+    /// Whether each transaction was approved, in the order given
+    pub fn approve_many(
+        env: Env,
+        transaction_ids: Vec<Symbol>,
+        approver: Address,
+    ) -> Result<Vec<bool>, MultisigError> {
+        approver.require_auth();
+        Self::extend_instance_ttl(&env);
+
+        let config = Self::get_config(env.clone());
+        if !config.owners.contains(&approver)
+            || !Self::is_signer_active(env.clone(), approver.clone())
+        {
+            return Err(MultisigError::Unauthorized);
+        }
+
+        let mut results = Vec::new(&env);
+        for transaction_id in transaction_ids.iter() {
+            let approved = Self::approve_internal(&env, transaction_id, approver.clone()).is_ok();
+            results.push_back(approved);
+        }
+
+        Ok(results)
+    }
+
+    fn approve_internal(
+        env: &Env,
+        transaction_id: Symbol,
+        approver: Address,
+    ) -> Result<bool, MultisigError> {
         let config = Self::get_config(env.clone());
 
-        if !config.owners.contains(&approver) {
+        if !config.owners.contains(&approver)
+            || !Self::is_signer_active(env.clone(), approver.clone())
+        {
             return Err(MultisigError::Unauthorized);
         }
 
-        let mut transaction: Transaction = env
-            .storage()
-            .persistent()
-            .get(&DataKey::Transaction(transaction_id.clone()))
-            .ok_or(MultisigError::TransactionNotFound)?;
+        let mut transaction = Self::get_transaction(env.clone(), transaction_id)?;
 
         if transaction.status != TransactionStatus::Pending {
             return Err(MultisigError::AlreadyExecuted);
         }
 
+        // Left for `expire_transaction`, which also updates the pending count
         if transaction.expires_at <= env.ledger().timestamp() {
-            transaction.status = TransactionStatus::Expired;
-            env.storage()
-                .persistent()
-                .set(&DataKey::Transaction(transaction_id), &transaction);
             return Err(MultisigError::InvalidTransaction);
         }
 
@@ -310,19 +481,153 @@ impl MultisigWalletContract {
             return Err(MultisigError::Unauthorized); // Reject self-approval
         }
 
-        transaction.confirmations.push_back(approver);
+        transaction.confirmations.push_back(approver.clone());
 
         if transaction.confirmations.len() >= transaction.required_confirmations {
-            transaction.status = TransactionStatus::Approved;
+            Self::set_status(env, &mut transaction, TransactionStatus::Approved);
         }
 
-        env.storage()
-            .persistent()
-            .set(&DataKey::Transaction(transaction_id), &transaction);
+        Self::save_transaction(env, &transaction);
+        Self::record_activity(env, &approver);
 
         Ok(true)
     }
 
+    /// Recompute a transaction's approval against the current signers
+    ///
+    /// Approvals from signers that have since been removed or deactivated
+    /// are not counted, and the current threshold replaces the one the
+    /// transaction was submitted under. An approved transaction that no
+    /// longer qualifies goes back to pending.
+    ///
+    /// # Arguments
+    ///
+    /// * `transaction_id` - Identifier for the transaction
+    ///
+    /// # Returns
+    ///
+    /// The transaction's status after reevaluation
+    pub fn reevaluate_transaction(
+        env: Env,
+        transaction_id: Symbol,
+    ) -> Result<TransactionStatus, MultisigError> {
+        Self::extend_instance_ttl(&env);
+        let mut transaction = Self::get_transaction(env.clone(), transaction_id)?;
+
+        if transaction.status != TransactionStatus::Pending
+            && transaction.status != TransactionStatus::Approved
+        {
+            return Err(MultisigError::AlreadyExecuted);
+        }
+
+        transaction.required_confirmations =
+            Self::required_for_amount(&env, &Self::get_config(env.clone()), transaction.amount);
+        let status = if Self::active_confirmations(&env, &transaction.confirmations)
+            >= transaction.required_confirmations
+        {
+            TransactionStatus::Approved
+        } else {
+            TransactionStatus::Pending
+        };
+        Self::set_status(&env, &mut transaction, status);
+        Self::save_transaction(&env, &transaction);
+
+        Ok(transaction.status)
+    }
+
+    /// Mark a transaction that passed its expiry without executing as expired
+    ///
+    /// Keeper entry point: anyone may call it. The transaction stops
+    /// counting towards the wallet's pending transactions.
+    ///
+    /// # Arguments
+    ///
+    /// * `transaction_id` - Identifier for the transaction
+    ///
+    /// # Returns
+    ///
+    /// True if the transaction was marked expired
+    pub fn expire_transaction(env: Env, transaction_id: Symbol) -> Result<bool, MultisigError> {
+        Self::extend_instance_ttl(&env);
+        let mut transaction = Self::get_transaction(env.clone(), transaction_id)?;
+
+        if transaction.status != TransactionStatus::Pending
+            && transaction.status != TransactionStatus::Approved
+        {
+            return Err(MultisigError::AlreadyExecuted);
+        }
+
+        if env.ledger().timestamp() <= transaction.expires_at {
+            return Err(MultisigError::InvalidTransaction);
+        }
+
+        Self::set_status(&env, &mut transaction, TransactionStatus::Expired);
+        Self::save_transaction(&env, &transaction);
+
+        Ok(true)
+    }
+
+    /// Check whether `execute_transaction` would currently succeed
+    ///
+    /// Runs the same checks as `execute_transaction` in the same order,
+    /// without changing any state.
+    ///
+    /// # Arguments
+    ///
+    /// * `transaction_id` - Identifier for the transaction
+    ///
+    /// # Returns
+    ///
+    /// Whether execution would succeed, and a reason code: `ready`,
+    /// `not_approved`, `inactive_signers`, `timelock` or `expired`
+    pub fn can_execute(env: Env, transaction_id: Symbol) -> Result<(bool, Symbol), MultisigError> {
+        let transaction = Self::get_transaction(env.clone(), transaction_id)?;
+        let config = Self::get_config(env.clone());
+        let now = env.ledger().timestamp();
+
+        let blocker = if transaction.status != TransactionStatus::Approved
+            && !(transaction.status == TransactionStatus::Pending
+                && transaction.confirmations.len() >= transaction.required_confirmations)
+        {
+            Some("not_approved")
+        } else if Self::active_confirmations(&env, &transaction.confirmations)
+            < transaction.required_confirmations
+        {
+            Some("inactive_signers")
+        } else if now < transaction.created_at.saturating_add(config.timelock) {
+            Some("timelock")
+        } else if now > transaction.expires_at {
+            Some("expired")
+        } else {
+            None
+        };
+
+        Ok(match blocker {
+            Some(reason) => (false, Symbol::new(&env, reason)),
+            None => (true, Symbol::new(&env, "ready")),
+        })
+    }
+
+    /// Get how long a transaction's timelock has left to run
+    ///
+    /// # Arguments
+    ///
+    /// * `transaction_id` - Identifier for the transaction
+    ///
+    /// # Returns
+    ///
+    /// Whether the timelock has elapsed, and the seconds remaining if not
+    pub fn get_timelock_status(
+        env: Env,
+        transaction_id: Symbol,
+    ) -> Result<(bool, u64), MultisigError> {
+        let transaction = Self::get_transaction(env.clone(), transaction_id)?;
+        let config = Self::get_config(env.clone());
+        let unlocks_at = transaction.created_at.saturating_add(config.timelock);
+        let remaining = unlocks_at.saturating_sub(env.ledger().timestamp());
+        Ok((remaining == 0, remaining))
+    }
+
     /// Execute an approved transaction
     ///
     /// # Arguments
@@ -333,11 +638,8 @@ impl MultisigWalletContract {
     ///
     /// True if execution was successful
     pub fn execute_transaction(env: Env, transaction_id: Symbol) -> Result<bool, MultisigError> {
-        let mut transaction: Transaction = env
-            .storage()
-            .persistent()
-            .get(&DataKey::Transaction(transaction_id.clone()))
-            .ok_or(MultisigError::TransactionNotFound)?;
+        Self::extend_instance_ttl(&env);
+        let mut transaction = Self::get_transaction(env.clone(), transaction_id.clone())?;
 
         if transaction.status != TransactionStatus::Approved {
             if transaction.status == TransactionStatus::Pending
@@ -351,25 +653,26 @@ impl MultisigWalletContract {
 
         let config = Self::get_config(env.clone());
 
+        // Signatures from signers deactivated since approving no longer count
+        if Self::active_confirmations(&env, &transaction.confirmations)
+            < transaction.required_confirmations
+        {
+            return Err(MultisigError::ThresholdNotMet);
+        }
+
         // Enforce timelock
-        if env.ledger().timestamp() < transaction.created_at + config.timelock {
+        if env.ledger().timestamp() < transaction.created_at.saturating_add(config.timelock) {
             return Err(MultisigError::WalletLocked);
         }
 
-        // Enforce expiration
+        // Enforce expiration; `expire_transaction` marks it expired
         if env.ledger().timestamp() > transaction.expires_at {
-            transaction.status = TransactionStatus::Expired;
-            env.storage()
-                .persistent()
-                .set(&DataKey::Transaction(transaction_id), &transaction);
             return Err(MultisigError::InvalidTransaction);
         }
 
         // Re-entry protection: update status before execution
-        transaction.status = TransactionStatus::Executed;
-        env.storage()
-            .persistent()
-            .set(&DataKey::Transaction(transaction_id.clone()), &transaction);
+        Self::set_status(&env, &mut transaction, TransactionStatus::Executed);
+        Self::save_transaction(&env, &transaction);
 
         // Execute the contract call
         if transaction.destination != env.current_contract_address() {
@@ -383,16 +686,85 @@ impl MultisigWalletContract {
             if transaction.function == Symbol::new(&env, "add_owner") {
                 let new_owner: Address = Address::from_val(&env, &transaction.data.get(0).unwrap());
                 let tx_id: Symbol = Symbol::from_val(&env, &transaction.data.get(1).unwrap());
-                let _ = Self::add_owner_internal(env.clone(), new_owner, tx_id);
+                // Optional trailing label and metadata URI for the new signer
+                let metadata = match (transaction.data.get(2), transaction.data.get(3)) {
+                    (Some(label), Some(metadata_uri)) => Some(SignerMetadata {
+                        label: String::from_val(&env, &label),
+                        metadata_uri: String::from_val(&env, &metadata_uri),
+                    }),
+                    _ => None,
+                };
+                let _ = Self::add_owner_internal(env.clone(), new_owner, metadata, tx_id);
             } else if transaction.function == Symbol::new(&env, "remove_owner") {
                 let owner_to_remove: Address =
                     Address::from_val(&env, &transaction.data.get(0).unwrap());
                 let tx_id: Symbol = Symbol::from_val(&env, &transaction.data.get(1).unwrap());
                 let _ = Self::remove_owner_internal(env.clone(), owner_to_remove, tx_id);
+            } else if transaction.function == Symbol::new(&env, "replace_owner") {
+                let old_owner: Address = Address::from_val(&env, &transaction.data.get(0).unwrap());
+                let new_owner: Address = Address::from_val(&env, &transaction.data.get(1).unwrap());
+                let tx_id: Symbol = Symbol::from_val(&env, &transaction.data.get(2).unwrap());
+                let _ = Self::replace_owner_internal(env.clone(), old_owner, new_owner, tx_id);
             } else if transaction.function == Symbol::new(&env, "change_threshold") {
                 let new_threshold: u32 = u32::from_val(&env, &transaction.data.get(0).unwrap());
                 let tx_id: Symbol = Symbol::from_val(&env, &transaction.data.get(1).unwrap());
                 let _ = Self::change_threshold_internal(env.clone(), new_threshold, tx_id);
+            } else if transaction.function == Symbol::new(&env, "update_signer_metadata") {
+                let signer: Address = Address::from_val(&env, &transaction.data.get(0).unwrap());
+                let label: String = String::from_val(&env, &transaction.data.get(1).unwrap());
+                let metadata_uri: String =
+                    String::from_val(&env, &transaction.data.get(2).unwrap());
+                let tx_id: Symbol = Symbol::from_val(&env, &transaction.data.get(3).unwrap());
+                let _ = Self::update_signer_metadata_internal(
+                    env.clone(),
+                    signer,
+                    label,
+                    metadata_uri,
+                    tx_id,
+                );
+            } else if transaction.function == Symbol::new(&env, "add_allowed_recipient") {
+                let recipient: Address = Address::from_val(&env, &transaction.data.get(0).unwrap());
+                let tx_id: Symbol = Symbol::from_val(&env, &transaction.data.get(1).unwrap());
+                let _ = Self::set_allowed_recipient_internal(env.clone(), recipient, true, tx_id);
+            } else if transaction.function == Symbol::new(&env, "remove_allowed_recipient") {
+                let recipient: Address = Address::from_val(&env, &transaction.data.get(0).unwrap());
+                let tx_id: Symbol = Symbol::from_val(&env, &transaction.data.get(1).unwrap());
+                let _ = Self::set_allowed_recipient_internal(env.clone(), recipient, false, tx_id);
+            } else if transaction.function == Symbol::new(&env, "set_signer_active") {
+                let signer: Address = Address::from_val(&env, &transaction.data.get(0).unwrap());
+                let active: bool = bool::from_val(&env, &transaction.data.get(1).unwrap());
+                let tx_id: Symbol = Symbol::from_val(&env, &transaction.data.get(2).unwrap());
+                let _ = Self::set_signer_active_internal(env.clone(), signer, active, tx_id);
+            } else if transaction.function == Symbol::new(&env, "set_allowlist_enabled") {
+                let enabled: bool = bool::from_val(&env, &transaction.data.get(0).unwrap());
+                let tx_id: Symbol = Symbol::from_val(&env, &transaction.data.get(1).unwrap());
+                let _ = Self::set_allowlist_enabled_internal(env.clone(), enabled, tx_id);
+            } else if transaction.function == Symbol::new(&env, "create_intent") {
+                let condition = IntentCondition::from_val(&env, &transaction.data.get(0).unwrap());
+                let token: Address = Address::from_val(&env, &transaction.data.get(1).unwrap());
+                let recipient: Address = Address::from_val(&env, &transaction.data.get(2).unwrap());
+                let amount: i128 = i128::from_val(&env, &transaction.data.get(3).unwrap());
+                let expires_at: u64 = u64::from_val(&env, &transaction.data.get(4).unwrap());
+                let tx_id: Symbol = Symbol::from_val(&env, &transaction.data.get(5).unwrap());
+                let _ = Self::create_intent_internal(
+                    env.clone(),
+                    condition,
+                    token,
+                    recipient,
+                    amount,
+                    expires_at,
+                    tx_id,
+                );
+            } else if transaction.function == Symbol::new(&env, "cancel_intent") {
+                let intent_id: u32 = u32::from_val(&env, &transaction.data.get(0).unwrap());
+                let tx_id: Symbol = Symbol::from_val(&env, &transaction.data.get(1).unwrap());
+                let _ = Self::cancel_intent_internal(env.clone(), intent_id, tx_id);
+            } else if transaction.function == Symbol::new(&env, "set_amount_threshold") {
+                let min_amount: i128 = i128::from_val(&env, &transaction.data.get(0).unwrap());
+                let required: u32 = u32::from_val(&env, &transaction.data.get(1).unwrap());
+                let tx_id: Symbol = Symbol::from_val(&env, &transaction.data.get(2).unwrap());
+                let _ =
+                    Self::set_amount_threshold_internal(env.clone(), min_amount, required, tx_id);
             }
         }
 
@@ -424,12 +796,64 @@ impl MultisigWalletContract {
         transaction_id: Symbol,
     ) -> Result<bool, MultisigError> {
         env.current_contract_address().require_auth();
-        Self::add_owner_internal(env, new_owner, transaction_id)
+        Self::add_owner_internal(env, new_owner, None, transaction_id)
     }
 
     fn add_owner_internal(
         env: Env,
         new_owner: Address,
+        metadata: Option<SignerMetadata>,
+        transaction_id: Symbol,
+    ) -> Result<bool, MultisigError> {
+        let tx = Self::get_transaction(env.clone(), transaction_id)?;
+        if tx.status != TransactionStatus::Executed {
+            return Err(MultisigError::Unauthorized);
+        }
+
+        let mut config = Self::get_config(env.clone());
+        if config.owners.contains(&new_owner) {
+            return Err(MultisigError::InvalidOwner);
+        }
+
+        if let Some(metadata) = metadata {
+            Self::save_signer_metadata(&env, &new_owner, &metadata)?;
+        }
+
+        config.owners.push_back(new_owner.clone());
+        env.storage().instance().set(&DataKey::Config, &config);
+        Self::log_admin_action(&env, &tx, "add_owner", new_owner.to_xdr(&env));
+
+        Ok(true)
+    }
+
+    /// Replace an owner with a new address in a single step
+    ///
+    /// The owner count and threshold are unchanged. The new owner starts
+    /// active, with no metadata or recorded activity.
+    ///
+    /// # Arguments
+    ///
+    /// * `old_owner` - Address of the owner to replace
+    /// * `new_owner` - Address taking its place
+    /// * `transaction_id` - Governing transaction ID
+    ///
+    /// # Returns
+    ///
+    /// True if the owner was replaced
+    pub fn replace_owner(
+        env: Env,
+        old_owner: Address,
+        new_owner: Address,
+        transaction_id: Symbol,
+    ) -> Result<bool, MultisigError> {
+        env.current_contract_address().require_auth();
+        Self::replace_owner_internal(env, old_owner, new_owner, transaction_id)
+    }
+
+    fn replace_owner_internal(
+        env: Env,
+        old_owner: Address,
+        new_owner: Address,
         transaction_id: Symbol,
     ) -> Result<bool, MultisigError> {
         let tx = Self::get_transaction(env.clone(), transaction_id)?;
@@ -438,12 +862,23 @@ impl MultisigWalletContract {
         }
 
         let mut config = Self::get_config(env.clone());
+        let index = config
+            .owners
+            .first_index_of(&old_owner)
+            .ok_or(MultisigError::InvalidOwner)?;
         if config.owners.contains(&new_owner) {
             return Err(MultisigError::InvalidOwner);
         }
 
-        config.owners.push_back(new_owner);
+        config.owners.set(index, new_owner.clone());
         env.storage().instance().set(&DataKey::Config, &config);
+        Self::clear_signer_state(&env, &old_owner);
+        Self::log_admin_action(
+            &env,
+            &tx,
+            "replace_owner",
+            (old_owner, new_owner).to_xdr(&env),
+        );
 
         Ok(true)
     }
@@ -493,16 +928,18 @@ impl MultisigWalletContract {
             return Err(MultisigError::InvalidOwner);
         }
 
-        if new_owners.len() < config.threshold {
+        if Self::count_active(&env, &new_owners) < Self::min_active_signers(&env, &config) {
             return Err(MultisigError::ThresholdNotMet);
         }
 
-        if new_owners.len() == 0 {
+        if new_owners.is_empty() {
             return Err(MultisigError::InvalidTransaction);
         }
 
         config.owners = new_owners;
         env.storage().instance().set(&DataKey::Config, &config);
+        Self::clear_signer_state(&env, &owner_to_remove);
+        Self::log_admin_action(&env, &tx, "remove_owner", owner_to_remove.to_xdr(&env));
 
         Ok(true)
     }
@@ -537,47 +974,933 @@ impl MultisigWalletContract {
         }
 
         let mut config = Self::get_config(env.clone());
-        if new_threshold == 0 || new_threshold > config.owners.len() {
+        if new_threshold == 0 || new_threshold > Self::count_active(&env, &config.owners) {
             return Err(MultisigError::InvalidTransaction);
         }
 
         config.threshold = new_threshold;
         env.storage().instance().set(&DataKey::Config, &config);
+        Self::log_admin_action(&env, &tx, "change_threshold", new_threshold.to_xdr(&env));
 
         Ok(true)
     }
 
-    /// Get transaction information
+    /// Activate or deactivate a signer without removing it
+    ///
+    /// A deactivated signer cannot submit or approve transactions, and its
+    /// existing approvals are ignored at execution.
     ///
     /// # Arguments
     ///
-    /// * `transaction_id` - Identifier for the transaction
+    /// * `signer` - Address of the signer
+    /// * `active` - Whether the signer may participate
+    /// * `transaction_id` - Governing transaction ID
     ///
     /// # Returns
     ///
-    /// Transaction data structure
-    pub fn get_transaction(env: Env, transaction_id: Symbol) -> Result<Transaction, MultisigError> {
-        env.storage()
-            .persistent()
-            .get(&DataKey::Transaction(transaction_id))
-            .ok_or(MultisigError::TransactionNotFound)
+    /// True if the signer's status was updated
+    pub fn set_signer_active(
+        env: Env,
+        signer: Address,
+        active: bool,
+        transaction_id: Symbol,
+    ) -> Result<bool, MultisigError> {
+        env.current_contract_address().require_auth();
+        Self::set_signer_active_internal(env, signer, active, transaction_id)
     }
 
-    /// Get wallet configuration
+    fn set_signer_active_internal(
+        env: Env,
+        signer: Address,
+        active: bool,
+        transaction_id: Symbol,
+    ) -> Result<bool, MultisigError> {
+        let tx = Self::get_transaction(env.clone(), transaction_id)?;
+        if tx.status != TransactionStatus::Executed {
+            return Err(MultisigError::Unauthorized);
+        }
+
+        let config = Self::get_config(env.clone());
+        if !config.owners.contains(&signer) {
+            return Err(MultisigError::InvalidOwner);
+        }
+
+        let key = DataKey::InactiveSigner(signer.clone());
+        if active {
+            env.storage().persistent().remove(&key);
+        } else {
+            let active_count = Self::count_active(&env, &config.owners)
+                - u32::from(Self::is_signer_active(env.clone(), signer.clone()));
+            if active_count < Self::min_active_signers(&env, &config) {
+                return Err(MultisigError::ThresholdNotMet);
+            }
+            env.storage().persistent().set(&key, &true);
+            Self::extend_persistent_ttl(&env, &key);
+        }
+        Self::log_admin_action(
+            &env,
+            &tx,
+            "set_signer_active",
+            (signer, active).to_xdr(&env),
+        );
+
+        Ok(true)
+    }
+
+    /// Check whether a signer is currently active
+    ///
+    /// # Arguments
+    ///
+    /// * `signer` - Address of the signer
     ///
     /// # Returns
     ///
-    /// Current wallet configuration
-    pub fn get_config(env: Env) -> MultisigConfig {
-        env.storage()
+    /// False if the signer has been deactivated
+    pub fn is_signer_active(env: Env, signer: Address) -> bool {
+        let key = DataKey::InactiveSigner(signer);
+        let inactive = env.storage().persistent().has(&key);
+        if inactive {
+            Self::extend_persistent_ttl(&env, &key);
+        }
+        !inactive
+    }
+
+    /// Get an aggregate view of the wallet's configuration and workload
+    ///
+    /// # Returns
+    ///
+    /// Wallet summary
+    pub fn get_wallet_summary(env: Env) -> WalletSummary {
+        let config = Self::get_config(env.clone());
+
+        WalletSummary {
+            owner_count: config.owners.len(),
+            active_signer_count: Self::count_active(&env, &config.owners),
+            threshold: config.threshold,
+            timelock: config.timelock,
+            max_transaction_amount: config.max_transaction_amount,
+            allowlist_enabled: config.allowlist_enabled,
+            pending_transactions: env
+                .storage()
+                .instance()
+                .get(&DataKey::PendingCount)
+                .unwrap_or(0),
+        }
+    }
+
+    /// Read the admin audit log of governance changes
+    ///
+    /// Only the most recent `ADMIN_LOG_SIZE` changes are retained. Reading
+    /// a change refreshes its TTL.
+    ///
+    /// # Arguments
+    ///
+    /// * `start` - Index of the first retained change to return, oldest first
+    /// * `limit` - Maximum number of changes to return
+    ///
+    /// # Returns
+    ///
+    /// Governance changes in the order they were made
+    pub fn get_admin_log(env: Env, start: u32, limit: u32) -> Vec<AdminAction> {
+        Self::extend_instance_ttl(&env);
+        let count: u32 = env
+            .storage()
             .instance()
-            .get(&DataKey::Config)
-            .unwrap_or_else(|| MultisigConfig {
-                owners: Vec::new(&env),
-                threshold: 0,
-                timelock: 0,
-                max_transaction_amount: 0,
-            })
+            .get(&DataKey::AdminLogCount)
+            .unwrap_or(0);
+        let oldest = count.saturating_sub(ADMIN_LOG_SIZE);
+        let mut actions = Vec::new(&env);
+        let first = oldest.saturating_add(start);
+        let end = first.saturating_add(limit).min(count);
+        for index in first..end {
+            let key = DataKey::AdminLog(index % ADMIN_LOG_SIZE);
+            if let Some(action) = env.storage().persistent().get(&key) {
+                Self::extend_persistent_ttl(&env, &key);
+                actions.push_back(action);
+            }
+        }
+        actions
+    }
+
+    /// Get when a signer last submitted or approved a transaction
+    ///
+    /// # Arguments
+    ///
+    /// * `signer` - Address of the signer
+    ///
+    /// # Returns
+    ///
+    /// Ledger timestamp of the signer's last activity, if any
+    pub fn get_signer_last_active(env: Env, signer: Address) -> Option<u64> {
+        let key = DataKey::SignerLastActive(signer);
+        let last_active = env.storage().persistent().get(&key);
+        if last_active.is_some() {
+            Self::extend_persistent_ttl(&env, &key);
+        }
+        last_active
+    }
+
+    /// List owners with no activity since a point in time
+    ///
+    /// # Arguments
+    ///
+    /// * `since` - Timestamp from which activity counts
+    ///
+    /// # Returns
+    ///
+    /// Owners that have not submitted or approved a transaction at or
+    /// after `since`
+    pub fn get_inactive_signers(env: Env, since: u64) -> Vec<Address> {
+        let config = Self::get_config(env.clone());
+        let mut inactive = Vec::new(&env);
+        for owner in config.owners.iter() {
+            match Self::get_signer_last_active(env.clone(), owner.clone()) {
+                Some(last_active) if last_active >= since => {}
+                _ => inactive.push_back(owner),
+            }
+        }
+        inactive
+    }
+
+    /// Require more approvals for transactions of at least a given amount
+    ///
+    /// Transactions submitted afterwards need the highest `required` of all
+    /// tiers whose `min_amount` they reach, and never fewer than the base
+    /// threshold. `required` must be below the number of active signers so
+    /// that enough owners besides the submitter remain to approve.
+    ///
+    /// # Arguments
+    ///
+    /// * `min_amount` - Smallest amount the tier applies to
+    /// * `required` - Approvals required in the tier; 0 removes the tier
+    /// * `transaction_id` - Governing transaction ID
+    ///
+    /// # Returns
+    ///
+    /// True if the tier was updated
+    pub fn set_amount_threshold(
+        env: Env,
+        min_amount: i128,
+        required: u32,
+        transaction_id: Symbol,
+    ) -> Result<bool, MultisigError> {
+        env.current_contract_address().require_auth();
+        Self::set_amount_threshold_internal(env, min_amount, required, transaction_id)
+    }
+
+    fn set_amount_threshold_internal(
+        env: Env,
+        min_amount: i128,
+        required: u32,
+        transaction_id: Symbol,
+    ) -> Result<bool, MultisigError> {
+        let tx = Self::get_transaction(env.clone(), transaction_id)?;
+        if tx.status != TransactionStatus::Executed {
+            return Err(MultisigError::Unauthorized);
+        }
+
+        // The submitter cannot approve its own transaction, so a tier needs
+        // more active signers than it requires approvals
+        let config = Self::get_config(env.clone());
+        if required > 0 && required >= Self::count_active(&env, &config.owners) {
+            return Err(MultisigError::InvalidTransaction);
+        }
+
+        let mut tiers = Self::get_amount_thresholds(env.clone());
+        if let Some(index) = tiers.iter().position(|(amount, _)| amount == min_amount) {
+            tiers.remove(index as u32);
+        }
+        if required > 0 {
+            tiers.push_back((min_amount, required));
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::AmountThresholds, &tiers);
+        Self::log_admin_action(
+            &env,
+            &tx,
+            "set_amount_threshold",
+            (min_amount, required).to_xdr(&env),
+        );
+
+        Ok(true)
+    }
+
+    /// Get the amount-based approval tiers
+    ///
+    /// # Returns
+    ///
+    /// `(min_amount, required)` pairs set with `set_amount_threshold`
+    pub fn get_amount_thresholds(env: Env) -> Vec<(i128, u32)> {
+        env.storage()
+            .instance()
+            .get(&DataKey::AmountThresholds)
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Pre-authorize a token transfer to run once an oracle condition holds
+    ///
+    /// The transfer is approved by the owners now and can later be made
+    /// by anyone through `execute_intent`, without a live quorum, until it
+    /// expires or is cancelled with `cancel_intent`.
+    ///
+    /// # Arguments
+    ///
+    /// * `condition` - Oracle price condition to wait on
+    /// * `token` - Token to transfer from the wallet
+    /// * `recipient` - Recipient of the transfer
+    /// * `amount` - Amount to transfer
+    /// * `expires_at` - Timestamp after which the intent can no longer execute
+    /// * `transaction_id` - Governing transaction ID
+    ///
+    /// # Returns
+    ///
+    /// Identifier of the new intent
+    pub fn create_intent(
+        env: Env,
+        condition: IntentCondition,
+        token: Address,
+        recipient: Address,
+        amount: i128,
+        expires_at: u64,
+        transaction_id: Symbol,
+    ) -> Result<u32, MultisigError> {
+        env.current_contract_address().require_auth();
+        Self::create_intent_internal(
+            env,
+            condition,
+            token,
+            recipient,
+            amount,
+            expires_at,
+            transaction_id,
+        )
+    }
+
+    fn create_intent_internal(
+        env: Env,
+        condition: IntentCondition,
+        token: Address,
+        recipient: Address,
+        amount: i128,
+        expires_at: u64,
+        transaction_id: Symbol,
+    ) -> Result<u32, MultisigError> {
+        let tx = Self::get_transaction(env.clone(), transaction_id)?;
+        if tx.status != TransactionStatus::Executed {
+            return Err(MultisigError::Unauthorized);
+        }
+
+        if expires_at <= env.ledger().timestamp() {
+            return Err(MultisigError::InvalidTransaction);
+        }
+
+        let intent_id: u32 = env
+            .storage()
+            .instance()
+            .get(&DataKey::IntentCount)
+            .unwrap_or(0);
+        let intent = Intent {
+            intent_id,
+            condition,
+            token,
+            recipient,
+            amount,
+            expires_at,
+            approvers: tx.confirmations,
+            executed: false,
+            cancelled: false,
+        };
+        Self::check_intent_policy(&env, &intent)?;
+        Self::save_intent(&env, &intent);
+        env.storage()
+            .instance()
+            .set(&DataKey::IntentCount, &(intent_id + 1));
+
+        Ok(intent_id)
+    }
+
+    /// Cancel an intent that has not executed
+    ///
+    /// # Arguments
+    ///
+    /// * `intent_id` - Identifier for the intent
+    /// * `transaction_id` - Governing transaction ID
+    ///
+    /// # Returns
+    ///
+    /// True if the intent was cancelled
+    pub fn cancel_intent(
+        env: Env,
+        intent_id: u32,
+        transaction_id: Symbol,
+    ) -> Result<bool, MultisigError> {
+        env.current_contract_address().require_auth();
+        Self::cancel_intent_internal(env, intent_id, transaction_id)
+    }
+
+    fn cancel_intent_internal(
+        env: Env,
+        intent_id: u32,
+        transaction_id: Symbol,
+    ) -> Result<bool, MultisigError> {
+        let tx = Self::get_transaction(env.clone(), transaction_id)?;
+        if tx.status != TransactionStatus::Executed {
+            return Err(MultisigError::Unauthorized);
+        }
+
+        let mut intent = Self::get_intent(env.clone(), intent_id)?;
+        if intent.executed || intent.cancelled {
+            return Err(MultisigError::AlreadyExecuted);
+        }
+
+        intent.cancelled = true;
+        Self::save_intent(&env, &intent);
+
+        Ok(true)
+    }
+
+    /// Make an intent's transfer if its oracle condition holds
+    ///
+    /// Keeper entry point: anyone may call it. The intent is checked
+    /// against the wallet's current amount limit, allowlist and signers,
+    /// then the oracle's `price(pair)` is read and the transfer is made
+    /// only if it is below the intent's threshold.
+    ///
+    /// # Arguments
+    ///
+    /// * `intent_id` - Identifier for the intent
+    ///
+    /// # Returns
+    ///
+    /// True if the transfer was made
+    pub fn execute_intent(env: Env, intent_id: u32) -> Result<bool, MultisigError> {
+        Self::extend_instance_ttl(&env);
+        let mut intent = Self::get_intent(env.clone(), intent_id)?;
+
+        if intent.executed || intent.cancelled {
+            return Err(MultisigError::AlreadyExecuted);
+        }
+
+        if env.ledger().timestamp() > intent.expires_at {
+            return Err(MultisigError::InvalidTransaction);
+        }
+
+        Self::check_intent_policy(&env, &intent)?;
+
+        let price: i128 = env.invoke_contract(
+            &intent.condition.oracle,
+            &Symbol::new(&env, "price"),
+            vec![&env, intent.condition.pair.into_val(&env)],
+        );
+        if price >= intent.condition.below {
+            return Err(MultisigError::ConditionNotMet);
+        }
+
+        // Re-entry protection: mark executed before transferring
+        intent.executed = true;
+        Self::save_intent(&env, &intent);
+
+        token::Client::new(&env, &intent.token).transfer(
+            &env.current_contract_address(),
+            &intent.recipient,
+            &intent.amount,
+        );
+
+        env.events().publish(
+            (Symbol::new(&env, "intent_executed"), intent_id),
+            (intent.recipient, intent.amount),
+        );
+
+        Ok(true)
+    }
+
+    /// Get intent information
+    ///
+    /// # Arguments
+    ///
+    /// * `intent_id` - Identifier for the intent
+    ///
+    /// # Returns
+    ///
+    /// Intent data structure
+    pub fn get_intent(env: Env, intent_id: u32) -> Result<Intent, MultisigError> {
+        let key = DataKey::Intent(intent_id);
+        let intent = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .ok_or(MultisigError::IntentNotFound)?;
+        Self::extend_persistent_ttl(&env, &key);
+        Ok(intent)
+    }
+
+    /// Add a destination to the recipient allowlist
+    ///
+    /// # Arguments
+    ///
+    /// * `recipient` - Address to allow as a transaction destination
+    /// * `transaction_id` - Governing transaction ID
+    ///
+    /// # Returns
+    ///
+    /// True if the recipient was allowlisted
+    pub fn add_allowed_recipient(
+        env: Env,
+        recipient: Address,
+        transaction_id: Symbol,
+    ) -> Result<bool, MultisigError> {
+        env.current_contract_address().require_auth();
+        Self::set_allowed_recipient_internal(env, recipient, true, transaction_id)
+    }
+
+    /// Remove a destination from the recipient allowlist
+    ///
+    /// # Arguments
+    ///
+    /// * `recipient` - Address to remove from the allowlist
+    /// * `transaction_id` - Governing transaction ID
+    ///
+    /// # Returns
+    ///
+    /// True if the recipient was removed
+    pub fn remove_allowed_recipient(
+        env: Env,
+        recipient: Address,
+        transaction_id: Symbol,
+    ) -> Result<bool, MultisigError> {
+        env.current_contract_address().require_auth();
+        Self::set_allowed_recipient_internal(env, recipient, false, transaction_id)
+    }
+
+    fn set_allowed_recipient_internal(
+        env: Env,
+        recipient: Address,
+        allowed: bool,
+        transaction_id: Symbol,
+    ) -> Result<bool, MultisigError> {
+        let tx = Self::get_transaction(env.clone(), transaction_id)?;
+        if tx.status != TransactionStatus::Executed {
+            return Err(MultisigError::Unauthorized);
+        }
+
+        let key = DataKey::RecipientAllowlist(recipient.clone());
+        if allowed {
+            env.storage().persistent().set(&key, &true);
+            Self::extend_persistent_ttl(&env, &key);
+        } else {
+            env.storage().persistent().remove(&key);
+        }
+        Self::log_admin_action(
+            &env,
+            &tx,
+            "set_allowed_recipient",
+            (recipient, allowed).to_xdr(&env),
+        );
+
+        Ok(true)
+    }
+
+    /// Turn enforcement of the recipient allowlist on or off
+    ///
+    /// # Arguments
+    ///
+    /// * `enabled` - Whether destinations must be allowlisted
+    /// * `transaction_id` - Governing transaction ID
+    ///
+    /// # Returns
+    ///
+    /// True if the setting was updated
+    pub fn set_allowlist_enabled(
+        env: Env,
+        enabled: bool,
+        transaction_id: Symbol,
+    ) -> Result<bool, MultisigError> {
+        env.current_contract_address().require_auth();
+        Self::set_allowlist_enabled_internal(env, enabled, transaction_id)
+    }
+
+    fn set_allowlist_enabled_internal(
+        env: Env,
+        enabled: bool,
+        transaction_id: Symbol,
+    ) -> Result<bool, MultisigError> {
+        let tx = Self::get_transaction(env.clone(), transaction_id)?;
+        if tx.status != TransactionStatus::Executed {
+            return Err(MultisigError::Unauthorized);
+        }
+
+        let mut config = Self::get_config(env.clone());
+        config.allowlist_enabled = enabled;
+        env.storage().instance().set(&DataKey::Config, &config);
+        Self::log_admin_action(&env, &tx, "set_allowlist_enabled", enabled.to_xdr(&env));
+
+        Ok(true)
+    }
+
+    /// Check whether an address is on the recipient allowlist
+    ///
+    /// # Arguments
+    ///
+    /// * `recipient` - Address to check
+    ///
+    /// # Returns
+    ///
+    /// True if the address is allowlisted
+    pub fn is_allowed_recipient(env: Env, recipient: Address) -> bool {
+        let key = DataKey::RecipientAllowlist(recipient);
+        let allowed = env.storage().persistent().has(&key);
+        if allowed {
+            Self::extend_persistent_ttl(&env, &key);
+        }
+        allowed
+    }
+
+    /// Set the label and metadata URI of an existing signer
+    ///
+    /// Labels are limited to 64 bytes and URIs to 256 bytes.
+    ///
+    /// # Arguments
+    ///
+    /// * `signer` - Address of the signer to describe
+    /// * `label` - Display label for the signer
+    /// * `metadata_uri` - URI pointing at off-chain contact metadata
+    /// * `transaction_id` - Governing transaction ID
+    ///
+    /// # Returns
+    ///
+    /// True if the metadata was updated
+    pub fn update_signer_metadata(
+        env: Env,
+        signer: Address,
+        label: String,
+        metadata_uri: String,
+        transaction_id: Symbol,
+    ) -> Result<bool, MultisigError> {
+        env.current_contract_address().require_auth();
+        Self::update_signer_metadata_internal(env, signer, label, metadata_uri, transaction_id)
+    }
+
+    fn update_signer_metadata_internal(
+        env: Env,
+        signer: Address,
+        label: String,
+        metadata_uri: String,
+        transaction_id: Symbol,
+    ) -> Result<bool, MultisigError> {
+        let tx = Self::get_transaction(env.clone(), transaction_id)?;
+        if tx.status != TransactionStatus::Executed {
+            return Err(MultisigError::Unauthorized);
+        }
+
+        let config = Self::get_config(env.clone());
+        if !config.owners.contains(&signer) {
+            return Err(MultisigError::InvalidOwner);
+        }
+
+        let metadata = SignerMetadata {
+            label,
+            metadata_uri,
+        };
+        Self::save_signer_metadata(&env, &signer, &metadata)?;
+        Self::log_admin_action(
+            &env,
+            &tx,
+            "update_signer_metadata",
+            (signer, metadata).to_xdr(&env),
+        );
+
+        Ok(true)
+    }
+
+    /// Get a signer's label and metadata URI
+    ///
+    /// # Arguments
+    ///
+    /// * `signer` - Address of the signer
+    ///
+    /// # Returns
+    ///
+    /// The signer's metadata, or `None` if none has been set
+    pub fn get_signer_metadata(env: Env, signer: Address) -> Option<SignerMetadata> {
+        let key = DataKey::SignerMetadata(signer);
+        let metadata = env.storage().persistent().get(&key);
+        if metadata.is_some() {
+            Self::extend_persistent_ttl(&env, &key);
+        }
+        metadata
+    }
+
+    /// Get transaction information
+    ///
+    /// # Arguments
+    ///
+    /// * `transaction_id` - Identifier for the transaction
+    ///
+    /// # Returns
+    ///
+    /// Transaction data structure
+    pub fn get_transaction(env: Env, transaction_id: Symbol) -> Result<Transaction, MultisigError> {
+        let transaction: Transaction = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Transaction(transaction_id))
+            .ok_or(MultisigError::TransactionNotFound)?;
+        Self::extend_transaction_ttl(&env, &transaction);
+        Ok(transaction)
+    }
+
+    /// Refresh the TTL of a stored transaction and of the wallet instance
+    ///
+    /// Keeper entry point: anyone may call it to keep a long-lived record
+    /// from being archived. The transaction is kept alive until its
+    /// `expires_at` plus a retention window.
+    ///
+    /// # Arguments
+    ///
+    /// * `transaction_id` - Identifier for the transaction
+    pub fn bump_entry(env: Env, transaction_id: Symbol) -> Result<(), MultisigError> {
+        Self::extend_instance_ttl(&env);
+        Self::get_transaction(env, transaction_id)?;
+        Ok(())
+    }
+
+    /// Get wallet configuration
+    ///
+    /// # Returns
+    ///
+    /// Current wallet configuration
+    pub fn get_config(env: Env) -> MultisigConfig {
+        env.storage()
+            .instance()
+            .get(&DataKey::Config)
+            .unwrap_or_else(|| MultisigConfig {
+                owners: Vec::new(&env),
+                threshold: 0,
+                timelock: 0,
+                max_transaction_amount: 0,
+                allowlist_enabled: false,
+            })
+    }
+
+    // --- Internal helpers ---
+
+    fn save_transaction(env: &Env, transaction: &Transaction) {
+        env.storage().persistent().set(
+            &DataKey::Transaction(transaction.transaction_id.clone()),
+            transaction,
+        );
+        Self::extend_transaction_ttl(env, transaction);
+    }
+
+    fn extend_transaction_ttl(env: &Env, transaction: &Transaction) {
+        let seconds_left = transaction
+            .expires_at
+            .saturating_sub(env.ledger().timestamp());
+        let extend_to = u32::try_from(seconds_left / LEDGER_CLOSE_SECONDS)
+            .unwrap_or(u32::MAX)
+            .saturating_add(TTL_RETENTION)
+            .min(env.storage().max_ttl());
+        env.storage().persistent().extend_ttl(
+            &DataKey::Transaction(transaction.transaction_id.clone()),
+            extend_to.saturating_sub(DAY_IN_LEDGERS),
+            extend_to,
+        );
+    }
+
+    /// Recipient of a token `transfer(from, to, amount)` or
+    /// `transfer_from(spender, from, to, amount)` call, if `function` is one.
+    fn transfer_recipient(
+        env: &Env,
+        function: &Symbol,
+        data: &Vec<Val>,
+    ) -> Result<Option<Address>, MultisigError> {
+        let index = if *function == Symbol::new(env, "transfer") {
+            1
+        } else if *function == Symbol::new(env, "transfer_from") {
+            2
+        } else {
+            return Ok(None);
+        };
+        let recipient = data.get(index).ok_or(MultisigError::InvalidTransaction)?;
+        Address::try_from_val(env, &recipient)
+            .map(Some)
+            .map_err(|_| MultisigError::InvalidTransaction)
+    }
+
+    /// Check an intent against the wallet's current policy: the amount
+    /// limit, the recipient allowlist and the approvals its amount requires
+    /// from owners that are still active.
+    fn check_intent_policy(env: &Env, intent: &Intent) -> Result<(), MultisigError> {
+        let config = Self::get_config(env.clone());
+        if intent.amount <= 0 || intent.amount > config.max_transaction_amount {
+            return Err(MultisigError::InvalidTransaction);
+        }
+
+        if config.allowlist_enabled
+            && !Self::is_allowed_recipient(env.clone(), intent.recipient.clone())
+        {
+            return Err(MultisigError::RecipientNotAllowed);
+        }
+
+        if Self::active_confirmations(env, &intent.approvers)
+            < Self::required_for_amount(env, &config, intent.amount)
+        {
+            return Err(MultisigError::ThresholdNotMet);
+        }
+
+        Ok(())
+    }
+
+    fn save_intent(env: &Env, intent: &Intent) {
+        let key = DataKey::Intent(intent.intent_id);
+        env.storage().persistent().set(&key, intent);
+        Self::extend_persistent_ttl(env, &key);
+    }
+
+    fn save_signer_metadata(
+        env: &Env,
+        signer: &Address,
+        metadata: &SignerMetadata,
+    ) -> Result<(), MultisigError> {
+        if metadata.label.len() > MAX_LABEL_LEN
+            || metadata.metadata_uri.len() > MAX_METADATA_URI_LEN
+        {
+            return Err(MultisigError::MetadataTooLong);
+        }
+
+        let key = DataKey::SignerMetadata(signer.clone());
+        env.storage().persistent().set(&key, metadata);
+        Self::extend_persistent_ttl(env, &key);
+        Ok(())
+    }
+
+    /// Move a transaction to a new status, counting it out of the pending
+    /// transactions when it leaves Pending or Approved for good.
+    fn set_status(env: &Env, transaction: &mut Transaction, status: TransactionStatus) {
+        let is_open =
+            |status| status == TransactionStatus::Pending || status == TransactionStatus::Approved;
+        if is_open(transaction.status) && !is_open(status) {
+            Self::track_pending(env, false);
+        }
+        transaction.status = status;
+    }
+
+    /// Count a transaction as submitted (`pending`) or as having left the
+    /// pending set.
+    fn track_pending(env: &Env, pending: bool) {
+        let count: u32 = env
+            .storage()
+            .instance()
+            .get(&DataKey::PendingCount)
+            .unwrap_or(0);
+        let count = if pending {
+            count.saturating_add(1)
+        } else {
+            count.saturating_sub(1)
+        };
+        env.storage().instance().set(&DataKey::PendingCount, &count);
+    }
+
+    /// Approvals required for a transaction of `amount`: the base threshold,
+    /// raised by any amount tier the transaction reaches.
+    fn required_for_amount(env: &Env, config: &MultisigConfig, amount: i128) -> u32 {
+        let mut required = config.threshold;
+        for (min_amount, tier_required) in Self::get_amount_thresholds(env.clone()).iter() {
+            if amount >= min_amount && tier_required > required {
+                required = tier_required;
+            }
+        }
+        required
+    }
+
+    /// Active signers the wallet must keep so that the threshold and every
+    /// amount tier can still be met. A tier needs one signer beyond its
+    /// required approvals, as the submitter cannot approve.
+    fn min_active_signers(env: &Env, config: &MultisigConfig) -> u32 {
+        let mut min = config.threshold;
+        for (_, required) in Self::get_amount_thresholds(env.clone()).iter() {
+            min = min.max(required.saturating_add(1));
+        }
+        min
+    }
+
+    /// Drop metadata, activation and activity records of a former owner.
+    fn clear_signer_state(env: &Env, signer: &Address) {
+        env.storage()
+            .persistent()
+            .remove(&DataKey::SignerMetadata(signer.clone()));
+        env.storage()
+            .persistent()
+            .remove(&DataKey::InactiveSigner(signer.clone()));
+        env.storage()
+            .persistent()
+            .remove(&DataKey::SignerLastActive(signer.clone()));
+    }
+
+    fn record_activity(env: &Env, signer: &Address) {
+        let key = DataKey::SignerLastActive(signer.clone());
+        env.storage()
+            .persistent()
+            .set(&key, &env.ledger().timestamp());
+        Self::extend_persistent_ttl(env, &key);
+    }
+
+    /// Append a governance change made by the executed transaction `tx` to
+    /// the admin audit log, overwriting the oldest entry once the log is full.
+    fn log_admin_action(env: &Env, tx: &Transaction, action: &str, data: Bytes) {
+        let count: u32 = env
+            .storage()
+            .instance()
+            .get(&DataKey::AdminLogCount)
+            .unwrap_or(0);
+        let entry = AdminAction {
+            action: Symbol::new(env, action),
+            actor: tx.creator.clone(),
+            timestamp: env.ledger().timestamp(),
+            data_hash: env.crypto().sha256(&data).into(),
+        };
+        let key = DataKey::AdminLog(count % ADMIN_LOG_SIZE);
+        env.storage().persistent().set(&key, &entry);
+        Self::extend_persistent_ttl(env, &key);
+        env.storage()
+            .instance()
+            .set(&DataKey::AdminLogCount, &count.saturating_add(1));
+    }
+
+    /// Number of `owners` not deactivated.
+    fn count_active(env: &Env, owners: &Vec<Address>) -> u32 {
+        let mut count = 0;
+        for owner in owners.iter() {
+            if Self::is_signer_active(env.clone(), owner) {
+                count += 1;
+            }
+        }
+        count
+    }
+
+    /// Confirmations from signers that are still active owners.
+    fn active_confirmations(env: &Env, confirmations: &Vec<Address>) -> u32 {
+        let owners = Self::get_config(env.clone()).owners;
+        let mut count = 0;
+        for signer in confirmations.iter() {
+            if owners.contains(&signer) && Self::is_signer_active(env.clone(), signer) {
+                count += 1;
+            }
+        }
+        count
+    }
+
+    fn extend_persistent_ttl(env: &Env, key: &DataKey) {
+        env.storage()
+            .persistent()
+            .extend_ttl(key, TTL_PERSISTENT - DAY_IN_LEDGERS, TTL_PERSISTENT);
+    }
+
+    fn extend_instance_ttl(env: &Env) {
+        env.storage()
+            .instance()
+            .extend_ttl(TTL_INSTANCE - DAY_IN_LEDGERS, TTL_INSTANCE);
     }
 }
 
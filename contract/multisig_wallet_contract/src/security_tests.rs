@@ -8,9 +8,14 @@
 //! - Key management vulnerabilities
 //! - Edge cases in multi-signature logic
 
-use crate::{MultisigWalletContract, MultisigWalletContractClient, TransactionStatus};
+use crate::{
+    DataKey, IntentCondition, MultisigError, MultisigWalletContract, MultisigWalletContractClient,
+    SignerMetadata, TransactionStatus,
+};
 use soroban_sdk::{
-    contract, contractimpl, testutils::Address as _, Address, Env, IntoVal, Symbol, Vec,
+    contract, contractimpl,
+    testutils::{storage::Persistent as _, Address as _, Ledger},
+    token, vec, Address, Env, IntoVal, String, Symbol, Val, Vec,
 };
 
 // ---------------------------------------------------------------------------
@@ -87,6 +92,25 @@ impl MaliciousReentrancyMultisigContract {
     }
 }
 
+#[contract]
+pub struct MockPriceOracle;
+
+#[contractimpl]
+impl MockPriceOracle {
+    pub fn set_price(env: Env, price: i128) {
+        env.storage()
+            .instance()
+            .set(&Symbol::new(&env, "price"), &price);
+    }
+
+    pub fn price(env: Env, _pair: Symbol) -> i128 {
+        env.storage()
+            .instance()
+            .get(&Symbol::new(&env, "price"))
+            .unwrap_or(0)
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Security Test Suite
 // ---------------------------------------------------------------------------
@@ -120,6 +144,28 @@ fn initialize_multisig_wallet(
     client.initialize(owners, &threshold, &0, &i128::MAX);
 }
 
+/// Submit, approve (by owners 1 and 2) and execute a call on the wallet itself.
+fn execute_self_call(
+    env: &Env,
+    client: &MultisigWalletContractClient,
+    owners: &Vec<Address>,
+    function: &str,
+    args: Vec<Val>,
+) -> Symbol {
+    let tx_id = client.submit_transaction(
+        &owners.get(0).unwrap(),
+        &client.address,
+        &0,
+        &Symbol::new(env, function),
+        &args,
+        &(env.ledger().timestamp() + 1000),
+    );
+    client.approve_transaction(&tx_id, &owners.get(1).unwrap());
+    client.approve_transaction(&tx_id, &owners.get(2).unwrap());
+    client.execute_transaction(&tx_id);
+    tx_id
+}
+
 // ---------------------------------------------------------------------------
 // Reentrancy Attack Tests
 // ---------------------------------------------------------------------------
@@ -311,4 +357,987 @@ mod tests {
         let config = client.get_config();
         assert!(config.owners.contains(&new_owner));
     }
+
+    // ---------------------------------------------------------------------------
+    // Storage Expiry Tests
+    // ---------------------------------------------------------------------------
+
+    fn transaction_ttl(
+        env: &Env,
+        client: &MultisigWalletContractClient,
+        transaction_id: &Symbol,
+    ) -> u32 {
+        env.as_contract(&client.address, || {
+            env.storage()
+                .persistent()
+                .get_ttl(&DataKey::Transaction(transaction_id.clone()))
+        })
+    }
+
+    #[test]
+    fn test_bumped_transaction_survives_ledger_advancement() {
+        let (env, owners, _, client) = create_test_env();
+        env.mock_all_auths();
+
+        initialize_multisig_wallet(&env, &client, &owners, 2);
+
+        let submit = |function: &str| {
+            client.submit_transaction(
+                &owners.get(0).unwrap(),
+                &Address::generate(&env),
+                &1000i128,
+                &Symbol::new(&env, function),
+                &Vec::new(&env),
+                &(env.ledger().timestamp() + 1000),
+            )
+        };
+        let bumped = submit("bumped");
+        let stale = submit("stale");
+
+        // Advance to just short of the records' archival ledger.
+        let initial_ttl = transaction_ttl(&env, &client, &bumped);
+        env.ledger()
+            .with_mut(|li| li.sequence_number += initial_ttl - 300);
+        assert_eq!(transaction_ttl(&env, &client, &stale), 300);
+
+        client.bump_entry(&bumped);
+        assert_eq!(transaction_ttl(&env, &client, &bumped), initial_ttl);
+
+        // Past the un-bumped record's live-until ledger, the bumped one is
+        // still comfortably live.
+        env.ledger().with_mut(|li| li.sequence_number += 1_000);
+        assert_eq!(transaction_ttl(&env, &client, &bumped), initial_ttl - 1_000);
+    }
+
+    // ---------------------------------------------------------------------------
+    // Signer Metadata Tests
+    // ---------------------------------------------------------------------------
+
+    #[test]
+    fn test_labeled_signer_added_and_relabelled() {
+        let (env, owners, _, client) = create_test_env();
+        env.mock_all_auths();
+
+        initialize_multisig_wallet(&env, &client, &owners, 2);
+
+        let new_owner = Address::generate(&env);
+        let mut args = Vec::new(&env);
+        args.push_back(new_owner.clone().into_val(&env));
+        args.push_back(Symbol::new(&env, "0").into_val(&env));
+        args.push_back(String::from_str(&env, "Treasurer").into_val(&env));
+        args.push_back(String::from_str(&env, "ipfs://treasurer").into_val(&env));
+        execute_self_call(&env, &client, &owners, "add_owner", args);
+
+        assert!(client.get_config().owners.contains(&new_owner));
+        assert_eq!(
+            client.get_signer_metadata(&new_owner),
+            Some(SignerMetadata {
+                label: String::from_str(&env, "Treasurer"),
+                metadata_uri: String::from_str(&env, "ipfs://treasurer"),
+            })
+        );
+
+        let mut args = Vec::new(&env);
+        args.push_back(new_owner.clone().into_val(&env));
+        args.push_back(String::from_str(&env, "Treasurer (EU)").into_val(&env));
+        args.push_back(String::from_str(&env, "ipfs://treasurer-eu").into_val(&env));
+        args.push_back(Symbol::new(&env, "1").into_val(&env));
+        execute_self_call(&env, &client, &owners, "update_signer_metadata", args);
+
+        let metadata = client.get_signer_metadata(&new_owner).unwrap();
+        assert_eq!(metadata.label, String::from_str(&env, "Treasurer (EU)"));
+        assert_eq!(
+            metadata.metadata_uri,
+            String::from_str(&env, "ipfs://treasurer-eu")
+        );
+        assert_eq!(client.get_signer_metadata(&owners.get(0).unwrap()), None);
+    }
+
+    #[test]
+    fn test_oversized_signer_metadata_rejected() {
+        let (env, owners, _, client) = create_test_env();
+        env.mock_all_auths();
+
+        initialize_multisig_wallet(&env, &client, &owners, 2);
+        let signer = owners.get(1).unwrap();
+        let governing_tx = execute_self_call(&env, &client, &owners, "any", Vec::new(&env));
+
+        let long_label = String::from_str(&env, &"x".repeat(65));
+        let result = client.try_update_signer_metadata(
+            &signer,
+            &long_label,
+            &String::from_str(&env, "ipfs://signer"),
+            &governing_tx,
+        );
+        assert_eq!(result, Err(Ok(MultisigError::MetadataTooLong)));
+
+        let long_uri = String::from_str(&env, &"x".repeat(257));
+        let result = client.try_update_signer_metadata(
+            &signer,
+            &String::from_str(&env, "Signer"),
+            &long_uri,
+            &governing_tx,
+        );
+        assert_eq!(result, Err(Ok(MultisigError::MetadataTooLong)));
+        assert_eq!(client.get_signer_metadata(&signer), None);
+    }
+
+    #[test]
+    fn test_replace_owner_keeps_owner_count() {
+        let (env, owners, _, client) = create_test_env();
+        env.mock_all_auths();
+
+        initialize_multisig_wallet(&env, &client, &owners, 2);
+        let old_owner = owners.get(2).unwrap();
+        let new_owner = Address::generate(&env);
+
+        let mut args = Vec::new(&env);
+        args.push_back(old_owner.into_val(&env));
+        args.push_back(new_owner.into_val(&env));
+        args.push_back(Symbol::new(&env, "0").into_val(&env));
+        let governing_tx = execute_self_call(&env, &client, &owners, "replace_owner", args);
+
+        let config = client.get_config();
+        assert_eq!(config.owners.len(), 3);
+        assert_eq!(config.threshold, 2);
+        assert!(!config.owners.contains(&old_owner));
+        assert_eq!(config.owners.get(2).unwrap(), new_owner);
+
+        // The replaced address is gone and the new one cannot be added twice.
+        let result = client.try_replace_owner(&old_owner, &new_owner, &governing_tx);
+        assert_eq!(result, Err(Ok(MultisigError::InvalidOwner)));
+        let result = client.try_replace_owner(&owners.get(0).unwrap(), &new_owner, &governing_tx);
+        assert_eq!(result, Err(Ok(MultisigError::InvalidOwner)));
+    }
+
+    #[test]
+    fn test_governance_changes_logged_in_order() {
+        let (env, owners, _, client) = create_test_env();
+        env.mock_all_auths();
+
+        initialize_multisig_wallet(&env, &client, &owners, 2);
+        let new_owner = Address::generate(&env);
+
+        let mut args = Vec::new(&env);
+        args.push_back(true.into_val(&env));
+        args.push_back(Symbol::new(&env, "0").into_val(&env));
+        execute_self_call(&env, &client, &owners, "set_allowlist_enabled", args);
+
+        let mut args = Vec::new(&env);
+        args.push_back(owners.get(2).unwrap().into_val(&env));
+        args.push_back(new_owner.into_val(&env));
+        args.push_back(Symbol::new(&env, "1").into_val(&env));
+        execute_self_call(&env, &client, &owners, "replace_owner", args);
+
+        // Owner 2 was replaced, so owners 0, 1 and the new owner approve.
+        let mut owners = client.get_config().owners;
+        owners.set(2, new_owner);
+        let mut args = Vec::new(&env);
+        args.push_back(3u32.into_val(&env));
+        args.push_back(Symbol::new(&env, "2").into_val(&env));
+        execute_self_call(&env, &client, &owners, "change_threshold", args);
+
+        let log = client.get_admin_log(&0, &10);
+        assert_eq!(log.len(), 3);
+        let actions: [&str; 3] = ["set_allowlist_enabled", "replace_owner", "change_threshold"];
+        for (entry, action) in log.iter().zip(actions) {
+            assert_eq!(entry.action, Symbol::new(&env, action));
+            assert_eq!(entry.actor, owners.get(0).unwrap());
+        }
+        assert_eq!(
+            client.get_admin_log(&1, &1).get(0).unwrap().action,
+            Symbol::new(&env, "replace_owner")
+        );
+    }
+
+    // ---------------------------------------------------------------------------
+    // Recipient Allowlist Tests
+    // ---------------------------------------------------------------------------
+
+    fn enable_allowlist_with(
+        env: &Env,
+        client: &MultisigWalletContractClient,
+        owners: &Vec<Address>,
+        recipient: &Address,
+    ) {
+        let mut args = Vec::new(env);
+        args.push_back(recipient.into_val(env));
+        args.push_back(Symbol::new(env, "0").into_val(env));
+        execute_self_call(env, client, owners, "add_allowed_recipient", args);
+
+        let mut args = Vec::new(env);
+        args.push_back(true.into_val(env));
+        args.push_back(Symbol::new(env, "1").into_val(env));
+        execute_self_call(env, client, owners, "set_allowlist_enabled", args);
+    }
+
+    #[test]
+    fn test_disallowed_recipient_rejected() {
+        let (env, owners, _, client) = create_test_env();
+        env.mock_all_auths();
+
+        initialize_multisig_wallet(&env, &client, &owners, 2);
+        enable_allowlist_with(&env, &client, &owners, &Address::generate(&env));
+
+        let result = client.try_submit_transaction(
+            &owners.get(0).unwrap(),
+            &Address::generate(&env),
+            &1000i128,
+            &Symbol::new(&env, "any"),
+            &Vec::new(&env),
+            &(env.ledger().timestamp() + 1000),
+        );
+        assert_eq!(result, Err(Ok(MultisigError::RecipientNotAllowed)));
+    }
+
+    #[test]
+    fn test_allowed_recipient_accepted() {
+        let (env, owners, _, client) = create_test_env();
+        env.mock_all_auths();
+
+        initialize_multisig_wallet(&env, &client, &owners, 2);
+        let vendor = Address::generate(&env);
+        enable_allowlist_with(&env, &client, &owners, &vendor);
+
+        assert!(client.get_config().allowlist_enabled);
+        assert!(client.is_allowed_recipient(&vendor));
+
+        let tx_id = client.submit_transaction(
+            &owners.get(0).unwrap(),
+            &vendor,
+            &1000i128,
+            &Symbol::new(&env, "any"),
+            &Vec::new(&env),
+            &(env.ledger().timestamp() + 1000),
+        );
+        assert_eq!(client.get_transaction(&tx_id).destination, vendor);
+    }
+
+    #[test]
+    fn test_token_transfer_to_disallowed_recipient_rejected() {
+        let (env, owners, _, client) = create_test_env();
+        env.mock_all_auths();
+
+        initialize_multisig_wallet(&env, &client, &owners, 2);
+        let vendor = Address::generate(&env);
+        enable_allowlist_with(&env, &client, &owners, &vendor);
+        let token = Address::generate(&env);
+
+        let transfer_to = |to: &Address| {
+            client.try_submit_transaction(
+                &owners.get(0).unwrap(),
+                &token,
+                &1000i128,
+                &Symbol::new(&env, "transfer"),
+                &vec![
+                    &env,
+                    client.address.into_val(&env),
+                    to.into_val(&env),
+                    1000i128.into_val(&env),
+                ],
+                &(env.ledger().timestamp() + 1000),
+            )
+        };
+
+        let result = transfer_to(&Address::generate(&env));
+        assert_eq!(result, Err(Ok(MultisigError::RecipientNotAllowed)));
+        assert!(transfer_to(&vendor).is_ok());
+    }
+
+    // ---------------------------------------------------------------------------
+    // Signer Activation Tests
+    // ---------------------------------------------------------------------------
+
+    #[test]
+    fn test_deactivated_signer_cannot_approve() {
+        let (env, owners, _, client) = create_test_env();
+        env.mock_all_auths();
+
+        initialize_multisig_wallet(&env, &client, &owners, 2);
+        let owner1 = owners.get(1).unwrap();
+        let owner2 = owners.get(2).unwrap();
+
+        let mut args = Vec::new(&env);
+        args.push_back(owner2.into_val(&env));
+        args.push_back(false.into_val(&env));
+        args.push_back(Symbol::new(&env, "0").into_val(&env));
+        let governing_tx = execute_self_call(&env, &client, &owners, "set_signer_active", args);
+        assert!(!client.is_signer_active(&owner2));
+        assert_eq!(client.get_config().owners.len(), 3);
+
+        // Only two signers remain active, so neither may be switched off.
+        let result = client.try_set_signer_active(&owner1, &false, &governing_tx);
+        assert_eq!(result, Err(Ok(MultisigError::ThresholdNotMet)));
+
+        let tx_id = client.submit_transaction(
+            &owners.get(0).unwrap(),
+            &Address::generate(&env),
+            &1000i128,
+            &Symbol::new(&env, "any"),
+            &Vec::new(&env),
+            &(env.ledger().timestamp() + 1000),
+        );
+        let result = client.try_approve_transaction(&tx_id, &owner2);
+        assert_eq!(result, Err(Ok(MultisigError::Unauthorized)));
+
+        client.set_signer_active(&owner2, &true, &governing_tx);
+        assert!(client.is_signer_active(&owner2));
+        client.approve_transaction(&tx_id, &owner2);
+    }
+
+    #[test]
+    fn test_owner_changes_count_only_active_signers() {
+        let (env, owners, _, client) = create_test_env();
+        env.mock_all_auths();
+
+        initialize_multisig_wallet(&env, &client, &owners, 2);
+        let owner1 = owners.get(1).unwrap();
+
+        let mut args = Vec::new(&env);
+        args.push_back(owners.get(2).unwrap().into_val(&env));
+        args.push_back(false.into_val(&env));
+        args.push_back(Symbol::new(&env, "0").into_val(&env));
+        let governing_tx = execute_self_call(&env, &client, &owners, "set_signer_active", args);
+
+        // Two owners would remain, but only one of them active.
+        let result = client.try_remove_owner(&owner1, &governing_tx);
+        assert_eq!(result, Err(Ok(MultisigError::ThresholdNotMet)));
+
+        // Three owners, but only two active signers to meet the threshold.
+        let result = client.try_change_threshold(&3, &governing_tx);
+        assert_eq!(result, Err(Ok(MultisigError::InvalidTransaction)));
+        assert_eq!(client.get_config().owners.len(), 3);
+        assert_eq!(client.get_config().threshold, 2);
+    }
+
+    // ---------------------------------------------------------------------------
+    // Execution Readiness Tests
+    // ---------------------------------------------------------------------------
+
+    #[test]
+    fn test_can_execute_reports_blocking_reason() {
+        let (env, owners, _, client) = create_test_env();
+        env.mock_all_auths();
+
+        client.initialize(&owners, &2, &600, &i128::MAX);
+        let start = env.ledger().timestamp();
+        let tx_id = client.submit_transaction(
+            &owners.get(0).unwrap(),
+            &Address::generate(&env),
+            &1000i128,
+            &Symbol::new(&env, "any"),
+            &Vec::new(&env),
+            &(start + 1000),
+        );
+
+        assert_eq!(
+            client.can_execute(&tx_id),
+            (false, Symbol::new(&env, "not_approved"))
+        );
+
+        client.approve_transaction(&tx_id, &owners.get(1).unwrap());
+        client.approve_transaction(&tx_id, &owners.get(2).unwrap());
+        assert_eq!(
+            client.can_execute(&tx_id),
+            (false, Symbol::new(&env, "timelock"))
+        );
+        assert_eq!(
+            client.try_execute_transaction(&tx_id),
+            Err(Ok(MultisigError::WalletLocked))
+        );
+
+        env.ledger().set_timestamp(start + 600);
+        assert_eq!(
+            client.can_execute(&tx_id),
+            (true, Symbol::new(&env, "ready"))
+        );
+
+        env.ledger().set_timestamp(start + 1001);
+        assert_eq!(
+            client.can_execute(&tx_id),
+            (false, Symbol::new(&env, "expired"))
+        );
+    }
+
+    // ---------------------------------------------------------------------------
+    // Batch Approval Tests
+    // ---------------------------------------------------------------------------
+
+    #[test]
+    fn test_approve_many_skips_already_signed() {
+        let (env, owners, _, client) = create_test_env();
+        env.mock_all_auths();
+
+        // Threshold 1: a single non-creator approval approves a transaction.
+        initialize_multisig_wallet(&env, &client, &owners, 1);
+        let approver = owners.get(1).unwrap();
+
+        let mut tx_ids = Vec::new(&env);
+        for _ in 0..3 {
+            tx_ids.push_back(client.submit_transaction(
+                &owners.get(0).unwrap(),
+                &Address::generate(&env),
+                &1000i128,
+                &Symbol::new(&env, "any"),
+                &Vec::new(&env),
+                &(env.ledger().timestamp() + 1000),
+            ));
+        }
+        client.approve_transaction(&tx_ids.get(1).unwrap(), &approver);
+
+        let results = client.approve_many(&tx_ids, &approver);
+        assert_eq!(results, vec![&env, true, false, true]);
+        for tx_id in tx_ids.iter() {
+            assert_eq!(
+                client.get_transaction(&tx_id).status,
+                TransactionStatus::Approved
+            );
+        }
+
+        let result = client.try_approve_many(&tx_ids, &Address::generate(&env));
+        assert_eq!(result, Err(Ok(MultisigError::Unauthorized)));
+    }
+
+    #[test]
+    fn test_approve_many_leaves_expired_transaction_to_expiry() {
+        let (env, owners, _, client) = create_test_env();
+        env.mock_all_auths();
+
+        initialize_multisig_wallet(&env, &client, &owners, 2);
+        let tx_id = client.submit_transaction(
+            &owners.get(0).unwrap(),
+            &Address::generate(&env),
+            &1000i128,
+            &Symbol::new(&env, "any"),
+            &Vec::new(&env),
+            &(env.ledger().timestamp() + 1000),
+        );
+
+        env.ledger().with_mut(|li| li.timestamp += 1001);
+        let results = client.approve_many(&vec![&env, tx_id.clone()], &owners.get(1).unwrap());
+        assert_eq!(results, vec![&env, false]);
+        assert_eq!(
+            client.get_transaction(&tx_id).status,
+            TransactionStatus::Pending
+        );
+        assert_eq!(client.get_wallet_summary().pending_transactions, 1);
+
+        client.expire_transaction(&tx_id);
+        assert_eq!(client.get_wallet_summary().pending_transactions, 0);
+    }
+
+    #[test]
+    fn test_timelock_status_counts_down() {
+        let (env, owners, _, client) = create_test_env();
+        env.mock_all_auths();
+
+        client.initialize(&owners, &2, &600, &i128::MAX);
+        let start = env.ledger().timestamp();
+        let tx_id = client.submit_transaction(
+            &owners.get(0).unwrap(),
+            &Address::generate(&env),
+            &1000i128,
+            &Symbol::new(&env, "any"),
+            &Vec::new(&env),
+            &(start + 1000),
+        );
+        assert_eq!(client.get_timelock_status(&tx_id), (false, 600));
+
+        env.ledger().set_timestamp(start + 450);
+        assert_eq!(client.get_timelock_status(&tx_id), (false, 150));
+
+        env.ledger().set_timestamp(start + 600);
+        assert_eq!(client.get_timelock_status(&tx_id), (true, 0));
+
+        env.ledger().set_timestamp(start + 900);
+        assert_eq!(client.get_timelock_status(&tx_id), (true, 0));
+    }
+
+    #[test]
+    fn test_overflowing_timelock_keeps_transaction_locked() {
+        let (env, owners, _, client) = create_test_env();
+        env.mock_all_auths();
+
+        client.initialize(&owners, &2, &u64::MAX, &i128::MAX);
+        let tx_id = client.submit_transaction(
+            &owners.get(0).unwrap(),
+            &Address::generate(&env),
+            &1000i128,
+            &Symbol::new(&env, "any"),
+            &Vec::new(&env),
+            &(env.ledger().timestamp() + 1000),
+        );
+        client.approve_transaction(&tx_id, &owners.get(1).unwrap());
+        client.approve_transaction(&tx_id, &owners.get(2).unwrap());
+
+        assert_eq!(
+            client.can_execute(&tx_id),
+            (false, Symbol::new(&env, "timelock"))
+        );
+        assert_eq!(
+            client.try_execute_transaction(&tx_id),
+            Err(Ok(MultisigError::WalletLocked))
+        );
+    }
+
+    // ---------------------------------------------------------------------------
+    // Signer Activity Tests
+    // ---------------------------------------------------------------------------
+
+    #[test]
+    fn test_dormant_signer_reported_inactive() {
+        let (env, owners, _, client) = create_test_env();
+        env.mock_all_auths();
+
+        initialize_multisig_wallet(&env, &client, &owners, 1);
+        env.ledger().set_timestamp(1_000);
+        let tx_id = client.submit_transaction(
+            &owners.get(0).unwrap(),
+            &Address::generate(&env),
+            &1000i128,
+            &Symbol::new(&env, "any"),
+            &Vec::new(&env),
+            &(env.ledger().timestamp() + 1000),
+        );
+        env.ledger().set_timestamp(1_500);
+        client.approve_transaction(&tx_id, &owners.get(1).unwrap());
+
+        assert_eq!(
+            client.get_signer_last_active(&owners.get(1).unwrap()),
+            Some(1_500)
+        );
+        assert_eq!(client.get_signer_last_active(&owners.get(2).unwrap()), None);
+
+        assert_eq!(
+            client.get_inactive_signers(&1_000),
+            vec![&env, owners.get(2).unwrap()]
+        );
+        assert_eq!(
+            client.get_inactive_signers(&1_200),
+            vec![&env, owners.get(0).unwrap(), owners.get(2).unwrap()]
+        );
+    }
+
+    #[test]
+    fn test_signer_activity_read_refreshes_its_ttl() {
+        let (env, owners, _, client) = create_test_env();
+        env.mock_all_auths();
+
+        initialize_multisig_wallet(&env, &client, &owners, 1);
+        let approver = owners.get(1).unwrap();
+        let tx_id = client.submit_transaction(
+            &owners.get(0).unwrap(),
+            &Address::generate(&env),
+            &1000i128,
+            &Symbol::new(&env, "any"),
+            &Vec::new(&env),
+            &(env.ledger().timestamp() + 1000),
+        );
+        client.approve_transaction(&tx_id, &approver);
+
+        let activity_ttl = || {
+            env.as_contract(&client.address, || {
+                env.storage()
+                    .persistent()
+                    .get_ttl(&DataKey::SignerLastActive(approver.clone()))
+            })
+        };
+        env.ledger()
+            .with_mut(|li| li.sequence_number += 17_280 * 60);
+        assert_eq!(activity_ttl(), 17_280 * 30);
+
+        assert!(client.get_signer_last_active(&approver).is_some());
+        assert_eq!(activity_ttl(), 17_280 * 90);
+    }
+
+    // ---------------------------------------------------------------------------
+    // Wallet Summary Tests
+    // ---------------------------------------------------------------------------
+
+    #[test]
+    fn test_wallet_summary_tracks_pending_transactions() {
+        let (env, owners, _, client) = create_test_env();
+        env.mock_all_auths();
+
+        client.initialize(&owners, &1, &0, &5_000);
+
+        let mut tx_ids = Vec::new(&env);
+        for _ in 0..3 {
+            tx_ids.push_back(client.submit_transaction(
+                &owners.get(0).unwrap(),
+                &client.address,
+                &0i128,
+                &Symbol::new(&env, "noop"),
+                &Vec::new(&env),
+                &(env.ledger().timestamp() + 1000),
+            ));
+        }
+        assert_eq!(client.get_wallet_summary().pending_transactions, 3);
+
+        let tx_id = tx_ids.get(0).unwrap();
+        client.approve_transaction(&tx_id, &owners.get(1).unwrap());
+        client.execute_transaction(&tx_id);
+
+        let summary = client.get_wallet_summary();
+        assert_eq!(summary.pending_transactions, 2);
+        assert_eq!(summary.owner_count, 3);
+        assert_eq!(summary.active_signer_count, 3);
+        assert_eq!(summary.threshold, 1);
+        assert_eq!(summary.max_transaction_amount, 5_000);
+        assert!(!summary.allowlist_enabled);
+    }
+
+    #[test]
+    fn test_expired_transaction_leaves_pending_count() {
+        let (env, owners, _, client) = create_test_env();
+        env.mock_all_auths();
+
+        initialize_multisig_wallet(&env, &client, &owners, 2);
+        let tx_id = client.submit_transaction(
+            &owners.get(0).unwrap(),
+            &client.address,
+            &0i128,
+            &Symbol::new(&env, "noop"),
+            &Vec::new(&env),
+            &(env.ledger().timestamp() + 1000),
+        );
+        assert_eq!(client.get_wallet_summary().pending_transactions, 1);
+
+        let result = client.try_expire_transaction(&tx_id);
+        assert_eq!(result, Err(Ok(MultisigError::InvalidTransaction)));
+
+        env.ledger().with_mut(|li| li.timestamp += 1001);
+        client.expire_transaction(&tx_id);
+        assert_eq!(
+            client.get_transaction(&tx_id).status,
+            TransactionStatus::Expired
+        );
+        assert_eq!(client.get_wallet_summary().pending_transactions, 0);
+
+        let result = client.try_expire_transaction(&tx_id);
+        assert_eq!(result, Err(Ok(MultisigError::AlreadyExecuted)));
+    }
+
+    #[test]
+    fn test_batch_approved_transactions_leave_pending_count() {
+        let (env, owners, _, client) = create_test_env();
+        env.mock_all_auths();
+
+        initialize_multisig_wallet(&env, &client, &owners, 1);
+        let mut tx_ids = Vec::new(&env);
+        for _ in 0..3 {
+            tx_ids.push_back(client.submit_transaction(
+                &owners.get(0).unwrap(),
+                &client.address,
+                &0i128,
+                &Symbol::new(&env, "noop"),
+                &Vec::new(&env),
+                &(env.ledger().timestamp() + 1000),
+            ));
+        }
+        client.approve_many(&tx_ids, &owners.get(1).unwrap());
+        assert_eq!(client.get_wallet_summary().pending_transactions, 3);
+
+        client.execute_transaction(&tx_ids.get(0).unwrap());
+        assert_eq!(client.get_wallet_summary().pending_transactions, 2);
+
+        // Executing past expiry fails without leaving the pending set.
+        env.ledger().with_mut(|li| li.timestamp += 1001);
+        let result = client.try_execute_transaction(&tx_ids.get(1).unwrap());
+        assert_eq!(result, Err(Ok(MultisigError::InvalidTransaction)));
+        assert_eq!(client.get_wallet_summary().pending_transactions, 2);
+
+        client.expire_transaction(&tx_ids.get(1).unwrap());
+        client.expire_transaction(&tx_ids.get(2).unwrap());
+        assert_eq!(client.get_wallet_summary().pending_transactions, 0);
+    }
+
+    // ---------------------------------------------------------------------------
+    // Reevaluation Tests
+    // ---------------------------------------------------------------------------
+
+    #[test]
+    fn test_removed_signer_approval_demotes_transaction() {
+        let (env, owners, _, client) = create_test_env();
+        env.mock_all_auths();
+
+        initialize_multisig_wallet(&env, &client, &owners, 2);
+        let tx_id = client.submit_transaction(
+            &owners.get(0).unwrap(),
+            &Address::generate(&env),
+            &1000i128,
+            &Symbol::new(&env, "any"),
+            &Vec::new(&env),
+            &(env.ledger().timestamp() + 1000),
+        );
+        client.approve_transaction(&tx_id, &owners.get(1).unwrap());
+        client.approve_transaction(&tx_id, &owners.get(2).unwrap());
+        assert_eq!(
+            client.get_transaction(&tx_id).status,
+            TransactionStatus::Approved
+        );
+
+        let mut args = Vec::new(&env);
+        args.push_back(owners.get(2).unwrap().into_val(&env));
+        args.push_back(Symbol::new(&env, "1").into_val(&env));
+        execute_self_call(&env, &client, &owners, "remove_owner", args);
+        assert_eq!(client.get_config().owners.len(), 2);
+
+        assert_eq!(
+            client.reevaluate_transaction(&tx_id),
+            TransactionStatus::Pending
+        );
+        assert_eq!(
+            client.get_transaction(&tx_id).status,
+            TransactionStatus::Pending
+        );
+        assert_eq!(
+            client.try_execute_transaction(&tx_id),
+            Err(Ok(MultisigError::ThresholdNotMet))
+        );
+    }
+
+    // ---------------------------------------------------------------------------
+    // Amount Threshold Tests
+    // ---------------------------------------------------------------------------
+
+    #[test]
+    fn test_large_transfer_needs_more_approvals() {
+        let (env, owners, _, client) = create_test_env();
+        env.mock_all_auths();
+
+        initialize_multisig_wallet(&env, &client, &owners, 1);
+        let mut args = Vec::new(&env);
+        args.push_back(10_000i128.into_val(&env));
+        args.push_back(2u32.into_val(&env));
+        args.push_back(Symbol::new(&env, "0").into_val(&env));
+        // With a threshold of 1 a single approval is enough to execute.
+        let tx_id = client.submit_transaction(
+            &owners.get(0).unwrap(),
+            &client.address,
+            &0,
+            &Symbol::new(&env, "set_amount_threshold"),
+            &args,
+            &(env.ledger().timestamp() + 1000),
+        );
+        client.approve_transaction(&tx_id, &owners.get(1).unwrap());
+        client.execute_transaction(&tx_id);
+        assert_eq!(
+            client.get_amount_thresholds(),
+            vec![&env, (10_000i128, 2u32)]
+        );
+
+        let submit = |amount: i128| {
+            client.submit_transaction(
+                &owners.get(0).unwrap(),
+                &Address::generate(&env),
+                &amount,
+                &Symbol::new(&env, "any"),
+                &Vec::new(&env),
+                &(env.ledger().timestamp() + 1000),
+            )
+        };
+        let small = submit(9_999);
+        let large = submit(10_000);
+
+        client.approve_transaction(&small, &owners.get(1).unwrap());
+        client.approve_transaction(&large, &owners.get(1).unwrap());
+        assert_eq!(
+            client.get_transaction(&small).status,
+            TransactionStatus::Approved
+        );
+        assert_eq!(
+            client.get_transaction(&large).status,
+            TransactionStatus::Pending
+        );
+
+        client.approve_transaction(&large, &owners.get(2).unwrap());
+        assert_eq!(
+            client.get_transaction(&large).status,
+            TransactionStatus::Approved
+        );
+    }
+
+    #[test]
+    fn test_owner_changes_keep_amount_tiers_reachable() {
+        let (env, owners, _, client) = create_test_env();
+        env.mock_all_auths();
+
+        initialize_multisig_wallet(&env, &client, &owners, 1);
+        let mut args = Vec::new(&env);
+        args.push_back(10_000i128.into_val(&env));
+        args.push_back(2u32.into_val(&env));
+        args.push_back(Symbol::new(&env, "0").into_val(&env));
+        let governing_tx = client.submit_transaction(
+            &owners.get(0).unwrap(),
+            &client.address,
+            &0,
+            &Symbol::new(&env, "set_amount_threshold"),
+            &args,
+            &(env.ledger().timestamp() + 1000),
+        );
+        client.approve_transaction(&governing_tx, &owners.get(1).unwrap());
+        client.execute_transaction(&governing_tx);
+        assert_eq!(
+            client.get_amount_thresholds(),
+            vec![&env, (10_000i128, 2u32)]
+        );
+        let owner2 = owners.get(2).unwrap();
+
+        // A tier of 3 could never be met: the submitter cannot approve.
+        let result = client.try_set_amount_threshold(&10_000, &3, &governing_tx);
+        assert_eq!(result, Err(Ok(MultisigError::InvalidTransaction)));
+
+        // Two signers would meet the base threshold of 1, but leave only one
+        // approver besides the submitter for the tier of 2.
+        let result = client.try_remove_owner(&owner2, &governing_tx);
+        assert_eq!(result, Err(Ok(MultisigError::ThresholdNotMet)));
+        let result = client.try_set_signer_active(&owner2, &false, &governing_tx);
+        assert_eq!(result, Err(Ok(MultisigError::ThresholdNotMet)));
+
+        let large = client.submit_transaction(
+            &owners.get(0).unwrap(),
+            &client.address,
+            &10_000,
+            &Symbol::new(&env, "noop"),
+            &Vec::new(&env),
+            &(env.ledger().timestamp() + 1000),
+        );
+        client.approve_transaction(&large, &owners.get(1).unwrap());
+        client.approve_transaction(&large, &owner2);
+        assert_eq!(
+            client.get_transaction(&large).status,
+            TransactionStatus::Approved
+        );
+        client.execute_transaction(&large);
+        assert_eq!(
+            client.get_transaction(&large).status,
+            TransactionStatus::Executed
+        );
+
+        client.set_amount_threshold(&10_000, &0, &governing_tx);
+        client.remove_owner(&owner2, &governing_tx);
+        assert_eq!(client.get_config().owners.len(), 2);
+    }
+
+    // ---------------------------------------------------------------------------
+    // Intent Tests
+    // ---------------------------------------------------------------------------
+
+    /// Deploy an oracle priced at 1_000 and a token with 5_000 held by the
+    /// wallet, and create intent 0 through governing transaction "0": pay
+    /// `vendor` 2_000 once the price falls below 900, within 1_000 seconds.
+    fn create_price_intent(
+        env: &Env,
+        client: &MultisigWalletContractClient,
+        owners: &Vec<Address>,
+        vendor: &Address,
+    ) -> (MockPriceOracleClient<'static>, token::Client<'static>) {
+        let oracle = env.register(MockPriceOracle, ());
+        let oracle_client = MockPriceOracleClient::new(env, &oracle);
+        oracle_client.set_price(&1_000);
+
+        let token = env
+            .register_stellar_asset_contract_v2(Address::generate(env))
+            .address();
+        token::StellarAssetClient::new(env, &token).mint(&client.address, &5_000);
+
+        let condition = IntentCondition {
+            oracle,
+            pair: Symbol::new(env, "XLM_USD"),
+            below: 900,
+        };
+        let mut args = Vec::new(env);
+        args.push_back(condition.into_val(env));
+        args.push_back(token.into_val(env));
+        args.push_back(vendor.into_val(env));
+        args.push_back(2_000i128.into_val(env));
+        args.push_back((env.ledger().timestamp() + 1000).into_val(env));
+        args.push_back(Symbol::new(env, "0").into_val(env));
+        execute_self_call(env, client, owners, "create_intent", args);
+
+        (oracle_client, token::Client::new(env, &token))
+    }
+
+    #[test]
+    fn test_intent_executes_once_oracle_crosses_threshold() {
+        let (env, owners, _, client) = create_test_env();
+        env.mock_all_auths();
+
+        initialize_multisig_wallet(&env, &client, &owners, 2);
+        let vendor = Address::generate(&env);
+        let (oracle_client, token_client) = create_price_intent(&env, &client, &owners, &vendor);
+
+        let result = client.try_execute_intent(&0);
+        assert_eq!(result, Err(Ok(MultisigError::ConditionNotMet)));
+        assert!(!client.get_intent(&0).executed);
+
+        oracle_client.set_price(&850);
+        client.execute_intent(&0);
+        assert!(client.get_intent(&0).executed);
+        assert_eq!(token_client.balance(&vendor), 2_000);
+        assert_eq!(token_client.balance(&client.address), 3_000);
+
+        let result = client.try_execute_intent(&0);
+        assert_eq!(result, Err(Ok(MultisigError::AlreadyExecuted)));
+    }
+
+    #[test]
+    fn test_cancelled_or_expired_intent_does_not_execute() {
+        let (env, owners, _, client) = create_test_env();
+        env.mock_all_auths();
+
+        initialize_multisig_wallet(&env, &client, &owners, 2);
+        let vendor = Address::generate(&env);
+        let (oracle_client, token_client) = create_price_intent(&env, &client, &owners, &vendor);
+        oracle_client.set_price(&850);
+
+        env.ledger().with_mut(|li| li.timestamp += 1001);
+        let result = client.try_execute_intent(&0);
+        assert_eq!(result, Err(Ok(MultisigError::InvalidTransaction)));
+
+        let mut args = Vec::new(&env);
+        args.push_back(0u32.into_val(&env));
+        args.push_back(Symbol::new(&env, "1").into_val(&env));
+        execute_self_call(&env, &client, &owners, "cancel_intent", args);
+        assert!(client.get_intent(&0).cancelled);
+
+        let result = client.try_execute_intent(&0);
+        assert_eq!(result, Err(Ok(MultisigError::AlreadyExecuted)));
+        assert_eq!(token_client.balance(&vendor), 0);
+    }
+
+    #[test]
+    fn test_intent_rechecks_wallet_policy_at_execution() {
+        let (env, owners, _, client) = create_test_env();
+        env.mock_all_auths();
+
+        initialize_multisig_wallet(&env, &client, &owners, 2);
+        let vendor = Address::generate(&env);
+        let (oracle_client, token_client) = create_price_intent(&env, &client, &owners, &vendor);
+        oracle_client.set_price(&850);
+
+        // The vendor is not on the allowlist once it is turned on.
+        let mut args = Vec::new(&env);
+        args.push_back(true.into_val(&env));
+        args.push_back(Symbol::new(&env, "1").into_val(&env));
+        execute_self_call(&env, &client, &owners, "set_allowlist_enabled", args);
+        let result = client.try_execute_intent(&0);
+        assert_eq!(result, Err(Ok(MultisigError::RecipientNotAllowed)));
+
+        let mut args = Vec::new(&env);
+        args.push_back(vendor.into_val(&env));
+        args.push_back(Symbol::new(&env, "2").into_val(&env));
+        execute_self_call(&env, &client, &owners, "add_allowed_recipient", args);
+
+        // Only one of the intent's two approvers is still active.
+        let mut args = Vec::new(&env);
+        args.push_back(owners.get(2).unwrap().into_val(&env));
+        args.push_back(false.into_val(&env));
+        args.push_back(Symbol::new(&env, "3").into_val(&env));
+        execute_self_call(&env, &client, &owners, "set_signer_active", args);
+        let result = client.try_execute_intent(&0);
+        assert_eq!(result, Err(Ok(MultisigError::ThresholdNotMet)));
+        assert_eq!(token_client.balance(&vendor), 0);
+    }
 }
@@ -25,13 +25,27 @@ pub struct ZKTicket {
     pub issued_at: u64,
 }
 
+/// Aggregate gate admissions for an event. Deliberately holds no ticket
+/// ids or owners so attendance can be reconciled without identifying anyone.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EntryLog {
+    pub count: u32,
+    pub last_entry_at: u64,
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 enum DataKey {
     Ticket(BytesN<32>),
     TicketNonce,
+    EntryLog(BytesN<32>),
 }
 
+const DAY_IN_LEDGERS: u32 = 17280;
+const TTL_INSTANCE: u32 = DAY_IN_LEDGERS * 30; // 30 days
+const TTL_PERSISTENT: u32 = DAY_IN_LEDGERS * 90; // 90 days
+
 #[contract]
 pub struct ZKTicketContract;
 
@@ -63,9 +77,7 @@ impl ZKTicketContract {
             issued_at: env.ledger().timestamp(),
         };
 
-        env.storage()
-            .persistent()
-            .set(&DataKey::Ticket(ticket_id.clone()), &ticket);
+        Self::save_ticket(&env, &ticket_id, &ticket);
 
         env.events().publish(
             (Symbol::new(&env, "ticket_issued"),),
@@ -109,9 +121,8 @@ impl ZKTicketContract {
         ticket.owner.require_auth();
         ticket.is_used = true;
 
-        env.storage()
-            .persistent()
-            .set(&DataKey::Ticket(ticket_id.clone()), &ticket);
+        Self::save_ticket(&env, &ticket_id, &ticket);
+        Self::record_entry(&env, &ticket.event_id);
 
         env.events().publish(
             (Symbol::new(&env, "ticket_used"),),
@@ -126,13 +137,72 @@ impl ZKTicketContract {
         Self::load_ticket(&env, &ticket_id)
     }
 
+    /// Number of tickets admitted at the gate for an event.
+    pub fn get_entry_count(env: Env, event_id: BytesN<32>) -> u32 {
+        Self::get_entry_log(env, event_id).count
+    }
+
+    /// Full entry log for an event: admission count and the timestamp of
+    /// the latest admission.
+    pub fn get_entry_log(env: Env, event_id: BytesN<32>) -> EntryLog {
+        env.storage()
+            .persistent()
+            .get(&DataKey::EntryLog(event_id))
+            .unwrap_or(EntryLog {
+                count: 0,
+                last_entry_at: 0,
+            })
+    }
+
+    /// Refresh the TTL of a stored ticket and of the contract instance.
+    ///
+    /// Keeper entry point: anyone may call it to keep an unused ticket from
+    /// being archived before its event.
+    pub fn bump_entry(env: Env, ticket_id: BytesN<32>) -> Result<(), ZKTicketError> {
+        Self::extend_instance_ttl(&env);
+        Self::load_ticket(&env, &ticket_id)?;
+        Ok(())
+    }
+
     // --- Internal helpers ---
 
     fn load_ticket(env: &Env, ticket_id: &BytesN<32>) -> Result<ZKTicket, ZKTicketError> {
+        let key = DataKey::Ticket(ticket_id.clone());
+        let ticket = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .ok_or(ZKTicketError::TicketNotFound)?;
+        Self::extend_persistent_ttl(env, &key);
+        Ok(ticket)
+    }
+
+    fn save_ticket(env: &Env, ticket_id: &BytesN<32>, ticket: &ZKTicket) {
+        let key = DataKey::Ticket(ticket_id.clone());
+        env.storage().persistent().set(&key, ticket);
+        Self::extend_persistent_ttl(env, &key);
+    }
+
+    fn record_entry(env: &Env, event_id: &BytesN<32>) {
+        let mut log = Self::get_entry_log(env.clone(), event_id.clone());
+        log.count = log.count.saturating_add(1);
+        log.last_entry_at = env.ledger().timestamp();
+
+        let key = DataKey::EntryLog(event_id.clone());
+        env.storage().persistent().set(&key, &log);
+        Self::extend_persistent_ttl(env, &key);
+    }
+
+    fn extend_persistent_ttl(env: &Env, key: &DataKey) {
         env.storage()
             .persistent()
-            .get(&DataKey::Ticket(ticket_id.clone()))
-            .ok_or(ZKTicketError::TicketNotFound)
+            .extend_ttl(key, TTL_PERSISTENT - DAY_IN_LEDGERS, TTL_PERSISTENT);
+    }
+
+    fn extend_instance_ttl(env: &Env) {
+        env.storage()
+            .instance()
+            .extend_ttl(TTL_INSTANCE - DAY_IN_LEDGERS, TTL_INSTANCE);
     }
 
     fn next_ticket_nonce(env: &Env) -> u64 {
@@ -143,6 +213,10 @@ impl ZKTicketContract {
             .unwrap_or(0);
         let next = current + 1;
         env.storage().instance().set(&DataKey::TicketNonce, &next);
+        Self::extend_instance_ttl(env);
         next
     }
 }
+
+#[cfg(test)]
+mod test;
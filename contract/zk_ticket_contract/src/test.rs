@@ -0,0 +1,73 @@
+use crate::{DataKey, ZKTicketContract, ZKTicketContractClient, TTL_PERSISTENT};
+use soroban_sdk::{
+    testutils::{storage::Persistent as _, Ledger},
+    BytesN, Env,
+};
+
+fn setup() -> (Env, ZKTicketContractClient<'static>) {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(ZKTicketContract, ());
+    let client = ZKTicketContractClient::new(&env, &contract_id);
+    (env, client)
+}
+
+fn ticket_ttl(env: &Env, client: &ZKTicketContractClient, ticket_id: &BytesN<32>) -> u32 {
+    env.as_contract(&client.address, || {
+        env.storage()
+            .persistent()
+            .get_ttl(&DataKey::Ticket(ticket_id.clone()))
+    })
+}
+
+// ---------------------------------------------------------------------------
+// Storage Expiry Tests
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_bumped_ticket_survives_ledger_advancement() {
+    let (env, client) = setup();
+    let event_id = BytesN::from_array(&env, &[1u8; 32]);
+
+    let bumped = client.issue_ticket(&event_id, &BytesN::from_array(&env, &[2u8; 32]));
+    let stale = client.issue_ticket(&event_id, &BytesN::from_array(&env, &[3u8; 32]));
+    assert_eq!(ticket_ttl(&env, &client, &bumped), TTL_PERSISTENT);
+
+    // Advance to just short of the tickets' archival ledger.
+    env.ledger()
+        .with_mut(|li| li.sequence_number += TTL_PERSISTENT - 300);
+    assert_eq!(ticket_ttl(&env, &client, &stale), 300);
+
+    client.bump_entry(&bumped);
+    assert_eq!(ticket_ttl(&env, &client, &bumped), TTL_PERSISTENT);
+
+    // Past the un-bumped ticket's live-until ledger, the bumped one is still
+    // comfortably live.
+    env.ledger().with_mut(|li| li.sequence_number += 1_000);
+    assert_eq!(ticket_ttl(&env, &client, &bumped), TTL_PERSISTENT - 1_000);
+}
+
+// ---------------------------------------------------------------------------
+// Entry Log Tests
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_entry_count_tracks_used_tickets() {
+    let (env, client) = setup();
+    let event_id = BytesN::from_array(&env, &[1u8; 32]);
+    let other_event = BytesN::from_array(&env, &[9u8; 32]);
+
+    let first = client.issue_ticket(&event_id, &BytesN::from_array(&env, &[2u8; 32]));
+    let second = client.issue_ticket(&event_id, &BytesN::from_array(&env, &[3u8; 32]));
+    client.issue_ticket(&other_event, &BytesN::from_array(&env, &[4u8; 32]));
+    assert_eq!(client.get_entry_count(&event_id), 0);
+
+    env.ledger().set_timestamp(1_000);
+    client.use_ticket(&first);
+    env.ledger().set_timestamp(2_000);
+    client.use_ticket(&second);
+
+    assert_eq!(client.get_entry_count(&event_id), 2);
+    assert_eq!(client.get_entry_log(&event_id).last_entry_at, 2_000);
+    assert_eq!(client.get_entry_count(&other_event), 0);
+}